@@ -6,6 +6,16 @@ use gear_lib_macros::*;
 declare_derive_storage_trait!(derive_nft_state, NFTStateKeeper, NFTStateField);
 declare_impl_trait!(derive_nft_core, NFTCore);
 declare_impl_trait!(derive_nft_metastate, NFTMetaState);
+declare_impl_trait!(derive_nft_rentable, NFTRentable);
+declare_impl_trait!(derive_nft_composable, NFTComposable);
+declare_impl_trait!(derive_nft_lockable, NFTLockable);
+declare_impl_trait!(derive_nft_metadata_core, NFTMetadataCore);
+declare_derive_storage_trait!(
+    derive_nft_marketplace_state,
+    NFTMarketplaceStateKeeper,
+    NFTMarketplaceStateField
+);
+declare_impl_trait!(derive_nft_marketplace, NFTMarketplace);
 
 // MultiToken
 declare_derive_storage_trait!(derive_mtk_state, StateKeeper, MTKStateKeeper);
@@ -16,3 +26,26 @@ declare_impl_trait!(derive_mtk_core, MTKCore);
 declare_derive_storage_trait!(derive_ft_state, FTStateKeeper, FTStateField);
 declare_impl_trait!(derive_ft_core, FTCore);
 declare_impl_trait!(derive_ft_metastate, FTMetaState);
+declare_impl_trait!(derive_ft_flash_mint, FTFlashMint);
+declare_impl_trait!(derive_ft_rebase, FTRebase);
+declare_derive_storage_trait!(
+    derive_ft_streams_state,
+    FTStreamsStateKeeper,
+    FTStreamsStateField
+);
+declare_impl_trait!(derive_ft_streams, FTStreams);
+declare_derive_storage_trait!(
+    derive_ft_fractions_state,
+    FTFractionsStateKeeper,
+    FTFractionsStateField
+);
+declare_impl_trait!(derive_ft_fractions, FTFractions);
+
+// Mint phases
+declare_derive_storage_trait!(
+    derive_mint_phases_state,
+    MintPhasesStateKeeper,
+    MintPhasesStateField
+);
+declare_impl_trait!(derive_nft_mint_phases, NFTMintPhases);
+declare_impl_trait!(derive_ft_mint_phases, FTMintPhases);
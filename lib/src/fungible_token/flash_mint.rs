@@ -0,0 +1,114 @@
+use crate::fungible_token::{io::*, state::*};
+use core::{future::Future, pin::Pin};
+use gstd::{exec, msg, prelude::*, ActorId};
+
+/// The value a flash loan receiver must reply with to accept the loan
+/// (blake2b-256 hash of `"ERC3156FlashBorrower.onFlashLoan"`).
+pub const FLASH_LOAN_CALLBACK_SUCCESS: [u8; 32] = [
+    0x64, 0xe7, 0xad, 0x50, 0x62, 0xeb, 0x9b, 0x99, 0xe0, 0x72, 0x65, 0x4d, 0x89, 0xd5, 0x22, 0x9c,
+    0xca, 0x32, 0xf7, 0x3f, 0x75, 0x1b, 0x7d, 0xad, 0x27, 0x3f, 0x44, 0x9b, 0xbe, 0x13, 0xe4, 0x2a,
+];
+
+pub trait FTFlashMint: FTStateKeeper {
+    /// Returns the maximum amount of token available for a flash loan
+    fn max_flash_loan(&self) -> u128 {
+        u128::MAX - self.get().total_supply
+    }
+
+    /// Returns the fee charged for a flash loan of `amount`
+    fn flash_fee(&self, _amount: u128) -> u128 {
+        0
+    }
+
+    /// Lends `amount` of freshly minted token to `receiver` for the duration of one
+    /// `OnFlashLoan` call (ERC-3156)
+    ///
+    /// The receiver MUST reply with `FLASH_LOAN_CALLBACK_SUCCESS` and hold `amount` plus the
+    /// fee afterwards, which are then burnt. Otherwise the minted amount is burnt back from the
+    /// receiver and `FTFlashLoanError` is replied.
+    ///
+    /// The state is saved while waiting for the reply, so the loan can't be rolled back and
+    /// tokens the receiver has moved away during the call can't be recovered. That's why only
+    /// programs from `FTState::flash_borrowers` trusted by the admin to repay can borrow.
+    ///
+    /// Flash loans are not available in the share-based mode, where the minted amount can't be
+    /// burnt back exactly.
+    ///
+    /// Requirements:
+    /// * the token MUST NOT be in the share-based mode
+    /// * `receiver` MUST be one of `FTState::flash_borrowers`
+    /// * `receiver` MUST NOT have another open flash loan
+    /// * `amount` MUST NOT exceed `max_flash_loan()`
+    ///
+    /// Arguments:
+    /// * `receiver`: A program that will receive the loan
+    /// * `amount`: The amount of token to be lent
+    /// * `data`: Arbitrary data passed to the receiver
+    fn flash_mint<'a>(
+        &'a mut self,
+        receiver: ActorId,
+        amount: u128,
+        data: Vec<u8>,
+    ) -> Pin<Box<dyn Future<Output = ()> + 'a>> {
+        Box::pin(async move {
+            if self.get().total_shares.is_some() {
+                panic!("Flash loans are not available in the share-based mode");
+            }
+            if !self.get().flash_borrowers.contains(&receiver) {
+                panic!("Receiver is not a trusted flash borrower");
+            }
+            if self.get().flash_loans.contains(&receiver) {
+                panic!("Receiver already has an open flash loan");
+            }
+            if amount > self.max_flash_loan() {
+                panic!("Amount exceeds the maximum flash loan");
+            }
+            let fee = self.flash_fee(amount);
+            let repayment = amount
+                .checked_add(fee)
+                .expect("Flash loan repayment overflow");
+
+            self.get_mut().flash_loans.insert(receiver);
+            self.get_mut().mint_balance(&receiver, amount);
+
+            let reply = msg::send_for_reply_as::<_, [u8; 32]>(
+                receiver,
+                OnFlashLoan {
+                    initiator: msg::source(),
+                    token: exec::program_id(),
+                    amount,
+                    fee,
+                    data,
+                },
+                0,
+                0,
+            )
+            .expect("Error during sending OnFlashLoan")
+            .await;
+            self.get_mut().flash_loans.remove(&receiver);
+
+            let balance = self.get().balance_of(&receiver);
+            let result = if reply.ok() != Some(FLASH_LOAN_CALLBACK_SUCCESS) {
+                Err(FTFlashLoanError::Rejected)
+            } else if balance < repayment {
+                Err(FTFlashLoanError::NotRepaid)
+            } else {
+                Ok(FTFlashLoan {
+                    receiver,
+                    amount,
+                    fee,
+                })
+            };
+            let burnt = if result.is_ok() {
+                repayment
+            } else {
+                balance.min(amount)
+            };
+            if burnt != 0 {
+                self.get_mut().burn_balance(&receiver, burnt);
+            }
+
+            msg::reply(result, 0).expect("Error during a reply with FTEvent::FTFlashLoan");
+        })
+    }
+}
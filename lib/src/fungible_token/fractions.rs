@@ -0,0 +1,332 @@
+use crate::fungible_token::{ft_core::*, io::*, state::*};
+use crate::{multitoken::io::TokenId as MTKTokenId, non_fungible_token::token::TokenId};
+use core::{future::Future, pin::Pin};
+use gstd::{exec, msg, prelude::*, ActorId};
+use primitive_types::U256;
+
+/// The token locked in the vault, held by the program in another NFT or MTK contract
+#[derive(Debug, Encode, Decode, PartialEq, Eq, Clone, Copy, TypeInfo)]
+pub enum FractionalizedToken {
+    NFT {
+        contract: ActorId,
+        token_id: TokenId,
+    },
+    MTK {
+        contract: ActorId,
+        token_id: MTKTokenId,
+    },
+}
+
+impl FractionalizedToken {
+    pub fn contract(&self) -> ActorId {
+        match self {
+            Self::NFT { contract, .. } | Self::MTK { contract, .. } => *contract,
+        }
+    }
+}
+
+#[derive(Default, Debug, Encode, Decode, PartialEq, Eq, Clone, Copy, TypeInfo)]
+pub struct Buyout {
+    pub bidder: ActorId,
+    /// The highest bid held on the program's balance.
+    pub bid: u128,
+    /// Auction end timestamp (in milliseconds).
+    pub end_time: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct FTFractionsState {
+    pub token: Option<FractionalizedToken>,
+    pub curator: ActorId,
+    /// The minimum bid starting a buyout auction.
+    pub reserve_price: u128,
+    /// Duration of a buyout auction (in milliseconds).
+    pub auction_duration: u64,
+    pub buyout: Option<Buyout>,
+    /// The value of the finished buyout not yet claimed by the share holders.
+    pub proceeds: u128,
+    /// The token is being transferred to or from the vault.
+    pub pending: bool,
+}
+
+pub trait FTFractionsStateKeeper {
+    fn get(&self) -> &FTFractionsState;
+    fn get_mut(&mut self) -> &mut FTFractionsState;
+}
+
+/// A vault splitting a token into the fungible shares of the program
+///
+/// The token leaves the vault either when one account redeems all the shares
+/// or when a buyout auction ends, then the shares are exchanged for the winning bid.
+/// The program is responsible for receiving the token before fractionalization,
+/// restricting `fractionalize` to the account that deposited it
+/// and sending the token to the account from `FTRelease`.
+/// A vault splitting a token into the fungible shares of the program
+///
+/// The token leaves the vault either when one account redeems all the shares
+/// or when a buyout auction ends, then the shares are exchanged for the winning bid.
+/// The token is moved by `FTTokenTransfer` messages to its contract, which must allow
+/// the program to transfer the token of the curator before fractionalization.
+/// The vault is locked while waiting for the reply of the contract.
+pub trait FTFractions: FTCore + FTFractionsStateKeeper {
+    /// Transfers the token from `msg::source()` to the program
+    /// and mints `supply` of shares to `msg::source()`, who becomes the curator
+    ///
+    /// Replies with `FTTokenTransferError` and leaves the vault empty
+    /// if the contract of the token doesn't confirm the transfer.
+    ///
+    /// Requirements:
+    /// * The vault MUST be empty: no token, shares or unclaimed proceeds
+    /// * `supply` MUST be non-zero
+    ///
+    /// Arguments:
+    /// * `token`: The token of `msg::source()`
+    /// * `supply`: The amount of shares to be minted
+    /// * `reserve_price`: The minimum bid starting a buyout auction
+    /// * `auction_duration`: The duration of a buyout auction (in milliseconds)
+    fn fractionalize<'a>(
+        &'a mut self,
+        token: FractionalizedToken,
+        supply: u128,
+        reserve_price: u128,
+        auction_duration: u64,
+    ) -> Pin<Box<dyn Future<Output = ()> + 'a>> {
+        Box::pin(async move {
+            let fractions = FTFractionsStateKeeper::get(self);
+            if fractions.token.is_some()
+                || fractions.pending
+                || fractions.proceeds != 0
+                || FTStateKeeper::get(self).total_supply != 0
+            {
+                panic!("Vault is not empty");
+            }
+            if supply == 0 {
+                panic!("Zero supply");
+            }
+            let curator = msg::source();
+            *FTFractionsStateKeeper::get_mut(self) = FTFractionsState {
+                token: None,
+                curator,
+                reserve_price,
+                auction_duration,
+                buyout: None,
+                proceeds: 0,
+                pending: true,
+            };
+
+            let transfer = FTTokenTransfer {
+                token,
+                from: curator,
+                to: exec::program_id(),
+            };
+            let transferred = transfer_token(transfer).await;
+            FTFractionsStateKeeper::get_mut(self).pending = false;
+
+            let result = if transferred {
+                FTFractionsStateKeeper::get_mut(self).token = Some(token);
+                FTStateKeeper::get_mut(self).mint_balance(&curator, supply);
+                Ok(FTFractionalization {
+                    token,
+                    curator,
+                    supply,
+                })
+            } else {
+                Err(FTTokenTransferError(transfer))
+            };
+            msg::reply(result, 0).expect("Error during a reply with FTEvent::FTFractionalization");
+        })
+    }
+
+    /// Places a bid of `msg::value()` in the buyout auction, starting it if there is none
+    ///
+    /// The previous bid is returned to its bidder.
+    ///
+    /// Requirements:
+    /// * The vault MUST hold the token and MUST NOT be transferring it
+    /// * The first bid MUST NOT be less than the reserve price
+    /// * The next bids MUST be higher than the current one before the auction ends
+    fn bid(&mut self) {
+        let fractions = FTFractionsStateKeeper::get(self);
+        if fractions.token.is_none() {
+            panic!("Vault holds no token");
+        }
+        if fractions.pending {
+            panic!("Token transfer is in progress");
+        }
+        let bid = msg::value();
+        let now = exec::block_timestamp();
+        let end_time = match fractions.buyout {
+            Some(buyout) => {
+                if now >= buyout.end_time {
+                    panic!("Buyout auction has ended");
+                }
+                if bid <= buyout.bid {
+                    panic!("Bid must be higher than the current one");
+                }
+                msg::send_bytes(buyout.bidder, b"", buyout.bid)
+                    .expect("Error during sending value");
+                buyout.end_time
+            }
+            None => {
+                if bid == 0 || bid < fractions.reserve_price {
+                    panic!("Bid is less than the reserve price");
+                }
+                now.saturating_add(fractions.auction_duration)
+            }
+        };
+        let bidder = msg::source();
+        FTFractionsStateKeeper::get_mut(self).buyout = Some(Buyout {
+            bidder,
+            bid,
+            end_time,
+        });
+        msg::reply(
+            FTBuyoutBid {
+                bidder,
+                bid,
+                end_time,
+            },
+            0,
+        )
+        .expect("Error during a reply with FTEvent::FTBuyoutBid");
+    }
+
+    /// Ends the buyout auction, transferring the token to the winner
+    /// and keeping the winning bid for the share holders
+    ///
+    /// Replies with `FTTokenTransferError` and keeps the auction
+    /// if the contract of the token doesn't confirm the transfer.
+    ///
+    /// Requirements:
+    /// * The buyout auction MUST have ended
+    /// * The vault MUST NOT be transferring the token
+    fn end_buyout<'a>(&'a mut self) -> Pin<Box<dyn Future<Output = ()> + 'a>> {
+        Box::pin(async move {
+            let fractions = FTFractionsStateKeeper::get(self);
+            let buyout = fractions.buyout.expect("No buyout auction");
+            if fractions.pending {
+                panic!("Token transfer is in progress");
+            }
+            if exec::block_timestamp() < buyout.end_time {
+                panic!("Buyout auction has not ended");
+            }
+            let token = fractions.token.expect("Vault holds no token");
+            FTFractionsStateKeeper::get_mut(self).pending = true;
+
+            let transfer = FTTokenTransfer {
+                token,
+                from: exec::program_id(),
+                to: buyout.bidder,
+            };
+            let transferred = transfer_token(transfer).await;
+            let fractions = FTFractionsStateKeeper::get_mut(self);
+            fractions.pending = false;
+
+            let result = if transferred {
+                fractions.token = None;
+                fractions.buyout = None;
+                fractions.proceeds = buyout.bid;
+                Ok(FTRelease {
+                    token,
+                    to: buyout.bidder,
+                })
+            } else {
+                Err(FTTokenTransferError(transfer))
+            };
+            msg::reply(result, 0).expect("Error during a reply with FTEvent::FTRelease");
+        })
+    }
+
+    /// Burns all the shares of `msg::source()` and sends the proportional part
+    /// of the buyout proceeds to it
+    ///
+    /// Requirements:
+    /// * The buyout auction MUST be ended
+    /// * `msg::source()` MUST hold shares
+    fn claim_proceeds(&mut self) {
+        if FTFractionsStateKeeper::get(self).token.is_some() {
+            panic!("Token is not bought out");
+        }
+        let account = msg::source();
+        let shares = FTStateKeeper::get(self).balance_of(&account);
+        if shares == 0 {
+            panic!("No shares to claim");
+        }
+        let total_supply = FTStateKeeper::get(self).total_supply;
+        let proceeds = FTFractionsStateKeeper::get(self).proceeds;
+        // the last holder gets the rounding remainder
+        let amount = (U256::from(proceeds) * U256::from(shares) / U256::from(total_supply))
+            .try_into()
+            .expect("Proceeds overflow");
+        FTStateKeeper::get_mut(self).burn_balance(&account, shares);
+        FTFractionsStateKeeper::get_mut(self).proceeds -= amount;
+        if amount != 0 {
+            msg::send_bytes(account, b"", amount).expect("Error during sending value");
+        }
+        msg::reply(
+            FTProceedsClaim {
+                account,
+                shares,
+                amount,
+            },
+            0,
+        )
+        .expect("Error during a reply with FTEvent::FTProceedsClaim");
+    }
+
+    /// Burns the whole supply of shares held by `msg::source()` and transfers the token to it
+    ///
+    /// Replies with `FTTokenTransferError` and gives the shares back
+    /// if the contract of the token doesn't confirm the transfer.
+    ///
+    /// Requirements:
+    /// * The vault MUST hold the token and have no buyout auction
+    /// * The vault MUST NOT be transferring the token
+    /// * `msg::source()` MUST hold all the shares
+    fn redeem<'a>(&'a mut self) -> Pin<Box<dyn Future<Output = ()> + 'a>> {
+        Box::pin(async move {
+            let fractions = FTFractionsStateKeeper::get(self);
+            let token = fractions.token.expect("Vault holds no token");
+            if fractions.buyout.is_some() {
+                panic!("Buyout auction is in progress");
+            }
+            if fractions.pending {
+                panic!("Token transfer is in progress");
+            }
+            let account = msg::source();
+            let total_supply = FTStateKeeper::get(self).total_supply;
+            if FTStateKeeper::get(self).balance_of(&account) != total_supply {
+                panic!("Not all shares are held by the account");
+            }
+            // the shares are burnt before the transfer so they can't be moved meanwhile
+            FTStateKeeper::get_mut(self).burn_balance(&account, total_supply);
+            FTFractionsStateKeeper::get_mut(self).pending = true;
+
+            let transfer = FTTokenTransfer {
+                token,
+                from: exec::program_id(),
+                to: account,
+            };
+            let transferred = transfer_token(transfer).await;
+            FTFractionsStateKeeper::get_mut(self).pending = false;
+
+            let result = if transferred {
+                FTFractionsStateKeeper::get_mut(self).token = None;
+                Ok(FTRelease { token, to: account })
+            } else {
+                FTStateKeeper::get_mut(self).mint_balance(&account, total_supply);
+                Err(FTTokenTransferError(transfer))
+            };
+            msg::reply(result, 0).expect("Error during a reply with FTEvent::FTRelease");
+        })
+    }
+}
+
+/// Sends `transfer` to the contract of the token and checks that it replied with the same message
+async fn transfer_token(transfer: FTTokenTransfer) -> bool {
+    let reply =
+        msg::send_for_reply_as::<_, FTTokenTransfer>(transfer.token.contract(), transfer, 0, 0)
+            .expect("Error during sending FTTokenTransfer")
+            .await;
+    reply.ok() == Some(transfer)
+}
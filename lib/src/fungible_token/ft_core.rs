@@ -10,12 +10,7 @@ pub trait FTCore: FTStateKeeper {
     /// Arguments:
     /// * `amount`: The amount of token to be minted (actually have no limit)
     fn mint(&mut self, to: &ActorId, amount: u128) {
-        self.get_mut()
-            .balances
-            .entry(*to)
-            .and_modify(|balance| *balance += amount)
-            .or_insert(amount);
-        self.get_mut().total_supply += amount;
+        self.get_mut().mint_balance(to, amount);
         msg::reply(
             FTTransfer {
                 from: ZERO_ID,
@@ -35,20 +30,7 @@ pub trait FTCore: FTStateKeeper {
     /// Arguments:
     /// `amount`: The amount of token to be burnt
     fn burn(&mut self, amount: u128) {
-        if self
-            .get()
-            .balances
-            .get(&msg::source())
-            .expect("The account has no balance at all")
-            < &amount
-        {
-            panic!("Amount exceeds account's balance");
-        }
-        self.get_mut()
-            .balances
-            .entry(msg::source())
-            .and_modify(|balance| *balance -= amount);
-        self.get_mut().total_supply -= amount;
+        self.get_mut().burn_balance(&msg::source(), amount);
         msg::reply(
             FTTransfer {
                 from: msg::source(),
@@ -78,24 +60,7 @@ pub trait FTCore: FTStateKeeper {
         if !self.can_transfer(from, amount) {
             panic!("Not allowed to transfer")
         }
-        if self
-            .get()
-            .balances
-            .get(from)
-            .expect("The account has no balance at all")
-            < &amount
-        {
-            panic!("Amount exceeds account's balance");
-        }
-        self.get_mut()
-            .balances
-            .entry(*from)
-            .and_modify(|balance| *balance -= amount);
-        self.get_mut()
-            .balances
-            .entry(*to)
-            .and_modify(|balance| *balance += amount)
-            .or_insert(amount);
+        self.get_mut().transfer_balance(from, to, amount);
         msg::reply(
             FTTransfer {
                 from: *from,
@@ -107,6 +72,51 @@ pub trait FTCore: FTStateKeeper {
         .expect("Error during a reply with FTEvent::FTTransfer");
     }
 
+    /// Mints tokens to several accounts at once
+    ///
+    /// Requirements:
+    /// * Every recipient MUST be a non-zero address
+    /// * The total amount MUST NOT overflow the total supply
+    ///
+    /// Arguments:
+    /// * `mints`: Pairs of recipients and amounts of token to be minted to them
+    fn batch_mint(&mut self, mints: Vec<(ActorId, u128)>) {
+        let total = self.get_mut().batch_mint_balance(&mints);
+        msg::reply(
+            FTBatchTransfer {
+                from: ZERO_ID,
+                recipients: u32::try_from(mints.len()).expect("Too many recipients"),
+                total,
+            },
+            0,
+        )
+        .expect("Error during a reply with FTEvent::FTBatchTransfer");
+    }
+
+    /// Transfers tokens from `msg::source()` to several accounts at once
+    ///
+    /// Either every transfer is applied or none of them.
+    ///
+    /// Requirements:
+    /// * Every recipient MUST be a non-zero address
+    /// * `msg::source()` MUST have enough tokens to cover the total amount
+    ///
+    /// Arguments:
+    /// * `transfers`: Pairs of recipients and amounts of token to be transferred to them
+    fn batch_transfer(&mut self, transfers: Vec<(ActorId, u128)>) {
+        let from = msg::source();
+        let total = self.get_mut().batch_transfer_balance(&from, &transfers);
+        msg::reply(
+            FTBatchTransfer {
+                from,
+                recipients: u32::try_from(transfers.len()).expect("Too many recipients"),
+                total,
+            },
+            0,
+        )
+        .expect("Error during a reply with FTEvent::FTBatchTransfer");
+    }
+
     /// Gives a right to another account to manage the `amount` of token
     ///
     /// Requirements:
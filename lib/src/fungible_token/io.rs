@@ -1,3 +1,4 @@
+use crate::fungible_token::fractions::FractionalizedToken;
 use gstd::{prelude::*, ActorId};
 
 #[derive(Debug, Encode, Decode, TypeInfo, PartialEq, Eq)]
@@ -13,3 +14,110 @@ pub struct FTApproval {
     pub to: ActorId,
     pub amount: u128,
 }
+
+#[derive(Debug, Encode, Decode, TypeInfo, PartialEq, Eq)]
+pub struct FTBatchTransfer {
+    pub from: ActorId,
+    /// The number of transfers in the batch.
+    pub recipients: u32,
+    /// The total amount of token transferred.
+    pub total: u128,
+}
+
+#[derive(Debug, Encode, Decode, TypeInfo, PartialEq, Eq)]
+pub struct OnFlashLoan {
+    pub initiator: ActorId,
+    pub token: ActorId,
+    pub amount: u128,
+    pub fee: u128,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, Encode, Decode, TypeInfo, PartialEq, Eq)]
+pub struct FTFlashLoan {
+    pub receiver: ActorId,
+    pub amount: u128,
+    pub fee: u128,
+}
+
+#[derive(Debug, Encode, Decode, TypeInfo, PartialEq, Eq, Clone, Copy)]
+pub enum FTFlashLoanError {
+    /// The receiver didn't reply with `FLASH_LOAN_CALLBACK_SUCCESS`.
+    Rejected,
+    /// The receiver didn't hold the amount plus the fee after the call.
+    NotRepaid,
+}
+
+#[derive(Debug, Encode, Decode, TypeInfo, PartialEq, Eq)]
+pub struct FTSupplyRebase {
+    pub old_total_supply: u128,
+    pub new_total_supply: u128,
+}
+
+#[derive(Debug, Encode, Decode, TypeInfo, PartialEq, Eq)]
+pub struct FTStreamCreation {
+    pub stream_id: u128,
+    pub sender: ActorId,
+    pub recipient: ActorId,
+    pub deposit: u128,
+    pub rate: u128,
+    pub start_time: u64,
+    pub stop_time: u64,
+}
+
+#[derive(Debug, Encode, Decode, TypeInfo, PartialEq, Eq)]
+pub struct FTStreamWithdrawal {
+    pub stream_id: u128,
+    pub recipient: ActorId,
+    pub amount: u128,
+}
+
+#[derive(Debug, Encode, Decode, TypeInfo, PartialEq, Eq)]
+pub struct FTStreamCancellation {
+    pub stream_id: u128,
+    pub recipient_amount: u128,
+    pub sender_amount: u128,
+}
+
+/// The message sent by the vault to the contract of the fractionalized token
+///
+/// The contract MUST transfer the token `from` -> `to` if the vault program is allowed to
+/// and reply with the same message.
+#[derive(Debug, Encode, Decode, TypeInfo, PartialEq, Eq, Clone, Copy)]
+pub struct FTTokenTransfer {
+    pub token: FractionalizedToken,
+    pub from: ActorId,
+    pub to: ActorId,
+}
+
+/// The contract of the token didn't confirm the transfer, the vault is left as it was.
+#[derive(Debug, Encode, Decode, TypeInfo, PartialEq, Eq, Clone, Copy)]
+pub struct FTTokenTransferError(pub FTTokenTransfer);
+
+#[derive(Debug, Encode, Decode, TypeInfo, PartialEq, Eq)]
+pub struct FTFractionalization {
+    pub token: FractionalizedToken,
+    pub curator: ActorId,
+    pub supply: u128,
+}
+
+#[derive(Debug, Encode, Decode, TypeInfo, PartialEq, Eq)]
+pub struct FTBuyoutBid {
+    pub bidder: ActorId,
+    pub bid: u128,
+    pub end_time: u64,
+}
+
+/// The token has been transferred from the vault to `to`.
+#[derive(Debug, Encode, Decode, TypeInfo, PartialEq, Eq)]
+pub struct FTRelease {
+    pub token: FractionalizedToken,
+    pub to: ActorId,
+}
+
+#[derive(Debug, Encode, Decode, TypeInfo, PartialEq, Eq)]
+pub struct FTProceedsClaim {
+    pub account: ActorId,
+    pub shares: u128,
+    pub amount: u128,
+}
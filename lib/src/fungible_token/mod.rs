@@ -1,3 +1,8 @@
+pub mod flash_mint;
+pub mod fractions;
 pub mod ft_core;
 pub mod io;
+pub mod rebase;
 pub mod state;
+pub mod streams;
+pub mod wrapped_native;
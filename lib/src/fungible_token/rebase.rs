@@ -0,0 +1,37 @@
+use crate::fungible_token::{io::*, state::*};
+use gstd::msg;
+
+pub trait FTRebase: FTStateKeeper {
+    /// Sets the total supply of the share-based token, changing the balances
+    /// of all holders proportionally to their shares
+    ///
+    /// Requirements:
+    /// * The share-based mode MUST be enabled (`FTState::total_shares` is `Some`)
+    /// * The pool MUST NOT be empty
+    /// * `new_total_supply` MUST be non-zero
+    ///
+    /// Arguments:
+    /// * `new_total_supply`: The total supply after the rebase
+    fn rebase(&mut self, new_total_supply: u128) {
+        let total_shares = self
+            .get()
+            .total_shares
+            .expect("Share-based mode is disabled");
+        if total_shares == 0 {
+            panic!("Nothing to rebase");
+        }
+        if new_total_supply == 0 {
+            panic!("Rebase to zero total supply");
+        }
+        let old_total_supply = self.get().total_supply;
+        self.get_mut().total_supply = new_total_supply;
+        msg::reply(
+            FTSupplyRebase {
+                old_total_supply,
+                new_total_supply,
+            },
+            0,
+        )
+        .expect("Error during a reply with FTEvent::FTSupplyRebase");
+    }
+}
@@ -1,5 +1,6 @@
 use gstd::{prelude::*, ActorId};
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
+use primitive_types::U256;
 
 #[derive(Debug, Default)]
 pub struct FTState {
@@ -11,10 +12,165 @@ pub struct FTState {
     pub total_supply: u128,
     /// Token's decimals.
     pub decimals: u8,
-    /// Token holders balances.
+    /// Token holders balances (shares of the pool in the share-based mode).
     pub balances: HashMap<ActorId, u128>,
     /// Token holders allowance to manipulate token amounts.
     pub allowances: HashMap<ActorId, HashMap<ActorId, u128>>,
+    /// Total amount of the pool shares.
+    /// `Some` enables the share-based mode where `balances` hold shares
+    /// and `total_supply` can be changed at once for everyone by a rebase.
+    pub total_shares: Option<u128>,
+    /// Programs trusted to repay flash loans, set by the program admin.
+    pub flash_borrowers: HashSet<ActorId>,
+    /// Receivers of flash loans waiting for their `OnFlashLoan` reply.
+    pub flash_loans: HashSet<ActorId>,
+}
+
+impl FTState {
+    /// Returns the amount of token held by `account`
+    pub fn balance_of(&self, account: &ActorId) -> u128 {
+        self.shares_to_amount(*self.balances.get(account).unwrap_or(&0))
+    }
+
+    /// Converts pool shares to the amount of token rounding down
+    pub fn shares_to_amount(&self, shares: u128) -> u128 {
+        match self.total_shares {
+            Some(total_shares) if total_shares != 0 => {
+                mul_div(shares, self.total_supply, total_shares, false)
+            }
+            _ => shares,
+        }
+    }
+
+    /// Converts the amount of token to pool shares
+    pub fn amount_to_shares(&self, amount: u128, round_up: bool) -> u128 {
+        match self.total_shares {
+            Some(total_shares) if total_shares != 0 && self.total_supply != 0 => {
+                mul_div(amount, total_shares, self.total_supply, round_up)
+            }
+            _ => amount,
+        }
+    }
+
+    /// Mints `amount` of token to `account`
+    ///
+    /// In the share-based mode shares are rounded down in favour of the pool
+    /// and the amount must be worth at least one share.
+    pub fn mint_balance(&mut self, account: &ActorId, amount: u128) {
+        let shares = self.amount_to_shares(amount, false);
+        self.total_supply = self
+            .total_supply
+            .checked_add(amount)
+            .expect("Total supply overflow");
+        if let Some(total_shares) = self.total_shares.as_mut() {
+            if shares == 0 {
+                panic!("Amount is too small to mint a share");
+            }
+            *total_shares = total_shares
+                .checked_add(shares)
+                .expect("Total shares overflow");
+        }
+        self.credit_shares(account, shares);
+    }
+
+    /// Burns `amount` of token from `account`
+    ///
+    /// In the share-based mode shares are rounded up in favour of the pool.
+    pub fn burn_balance(&mut self, account: &ActorId, amount: u128) {
+        let shares = self.debited_shares(account, amount);
+        self.balances
+            .entry(*account)
+            .and_modify(|balance| *balance -= shares);
+        self.total_supply -= amount;
+        if let Some(total_shares) = self.total_shares.as_mut() {
+            *total_shares -= shares;
+            // the dust left after the last shares are burnt belongs to nobody
+            if *total_shares == 0 {
+                self.total_supply = 0;
+            }
+        }
+    }
+
+    /// Transfers `amount` of token from `from` to `to`
+    ///
+    /// In the share-based mode shares are rounded up in favour of the recipient.
+    pub fn transfer_balance(&mut self, from: &ActorId, to: &ActorId, amount: u128) {
+        let shares = self.debited_shares(from, amount);
+        self.balances
+            .entry(*from)
+            .and_modify(|balance| *balance -= shares);
+        self.credit_shares(to, shares);
+    }
+
+    /// Mints token to several accounts and returns the total amount minted
+    ///
+    /// Nothing is minted if any recipient is invalid or the total supply overflows.
+    pub fn batch_mint_balance(&mut self, mints: &[(ActorId, u128)]) -> u128 {
+        let total = batch_total(mints);
+        if self.total_supply.checked_add(total).is_none() {
+            panic!("Total supply overflow");
+        }
+        for (to, amount) in mints {
+            self.mint_balance(to, *amount);
+        }
+        total
+    }
+
+    /// Transfers token from `from` to several accounts and returns the total amount transferred
+    ///
+    /// Nothing is transferred if any recipient is invalid or `from` can't cover the total.
+    pub fn batch_transfer_balance(
+        &mut self,
+        from: &ActorId,
+        transfers: &[(ActorId, u128)],
+    ) -> u128 {
+        let total = batch_total(transfers);
+        if self.balance_of(from) < total {
+            panic!("Amount exceeds account's balance");
+        }
+        for (to, amount) in transfers {
+            self.transfer_balance(from, to, *amount);
+        }
+        total
+    }
+
+    fn credit_shares(&mut self, account: &ActorId, shares: u128) {
+        let balance = self.balances.entry(*account).or_default();
+        *balance = balance.checked_add(shares).expect("Balance overflow");
+    }
+
+    fn debited_shares(&self, account: &ActorId, amount: u128) -> u128 {
+        let shares = *self
+            .balances
+            .get(account)
+            .expect("The account has no balance at all");
+        if self.shares_to_amount(shares) < amount {
+            panic!("Amount exceeds account's balance");
+        }
+        // `amount <= shares * total_supply / total_shares` so the rounded up shares fit
+        self.amount_to_shares(amount, true)
+    }
+}
+
+/// Validates the batch recipients and returns the total amount of the batch
+fn batch_total(batch: &[(ActorId, u128)]) -> u128 {
+    batch.iter().fold(0u128, |total, (to, amount)| {
+        if to == &ActorId::zero() {
+            panic!("Zero addresses");
+        }
+        total.checked_add(*amount).expect("Batch total overflow")
+    })
+}
+
+fn mul_div(a: u128, b: u128, c: u128, round_up: bool) -> u128 {
+    let product = U256::from(a) * U256::from(b);
+    let (quotient, remainder) = product.div_mod(U256::from(c));
+    let quotient = if round_up && !remainder.is_zero() {
+        quotient + 1
+    } else {
+        quotient
+    };
+    quotient.try_into().expect("Share conversion overflow")
 }
 
 pub trait FTStateKeeper {
@@ -48,9 +204,86 @@ pub trait FTMetaState: FTStateKeeper {
             FTQuery::Decimals => FTQueryReply::Decimals(self.get().decimals),
             FTQuery::TotalSupply => FTQueryReply::TotalSupply(self.get().total_supply),
             FTQuery::BalanceOf { account } => {
-                FTQueryReply::BalanceOf(*self.get().balances.get(&account).unwrap_or(&0))
+                FTQueryReply::BalanceOf(self.get().balance_of(&account))
             }
         };
         Some(reply.encode())
     }
 }
+
+/// Native checks of the batch accounting and the event size,
+/// the gas of `FTCore` batches isn't measured here.
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fungible_token::io::FTBatchTransfer;
+
+    const RECIPIENTS: u64 = 1_000;
+
+    fn batch() -> Vec<(ActorId, u128)> {
+        (1..=RECIPIENTS)
+            .map(|i| (ActorId::from(i), i as u128))
+            .collect()
+    }
+
+    #[test]
+    fn batch_transfer_to_1k_recipients() {
+        let from = ActorId::from(u64::MAX);
+        let mut state = FTState::default();
+        state.mint_balance(&from, 1_000_000);
+
+        let total = state.batch_transfer_balance(&from, &batch());
+
+        assert_eq!(total, (1..=RECIPIENTS as u128).sum::<u128>());
+        assert_eq!(state.balance_of(&from), 1_000_000 - total);
+        for (to, amount) in batch() {
+            assert_eq!(state.balance_of(&to), amount);
+        }
+        assert_eq!(state.total_supply, 1_000_000);
+    }
+
+    #[test]
+    fn batch_mint_to_1k_recipients() {
+        let mut state = FTState::default();
+
+        let total = state.batch_mint_balance(&batch());
+
+        assert_eq!(state.total_supply, total);
+        for (to, amount) in batch() {
+            assert_eq!(state.balance_of(&to), amount);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Amount exceeds account's balance")]
+    fn batch_transfer_is_all_or_nothing() {
+        let from = ActorId::from(u64::MAX);
+        let mut state = FTState::default();
+        state.mint_balance(&from, 100);
+
+        state.batch_transfer_balance(&from, &batch());
+    }
+
+    #[test]
+    #[should_panic(expected = "Amount is too small to mint a share")]
+    fn mint_worth_no_shares_fails() {
+        let mut state = FTState {
+            total_supply: 3,
+            total_shares: Some(2),
+            ..Default::default()
+        };
+
+        state.mint_balance(&ActorId::from(1), 1);
+    }
+
+    #[test]
+    fn batch_event_size_does_not_depend_on_recipients() {
+        let event = FTBatchTransfer {
+            from: ActorId::zero(),
+            recipients: RECIPIENTS as u32,
+            total: u128::MAX,
+        };
+        // actor ID, recipients count and total
+        assert_eq!(event.encode().len(), 32 + 4 + 16);
+    }
+}
@@ -0,0 +1,223 @@
+use crate::fungible_token::{ft_core::*, io::*, state::*};
+use gstd::{exec, msg, prelude::*, ActorId};
+use hashbrown::HashMap;
+
+const ZERO_ID: ActorId = ActorId::zero();
+
+pub type StreamId = u128;
+
+#[derive(Default, Debug, Encode, Decode, PartialEq, Eq, Clone, TypeInfo)]
+pub struct Stream {
+    pub sender: ActorId,
+    pub recipient: ActorId,
+    /// Amount of token locked for the whole stream.
+    pub deposit: u128,
+    /// Amount of token streamed per second.
+    pub rate: u128,
+    /// Stream start timestamp (in milliseconds).
+    pub start_time: u64,
+    /// Stream stop timestamp (in milliseconds).
+    pub stop_time: u64,
+    /// Amount of token already withdrawn by the recipient.
+    pub withdrawn: u128,
+}
+
+impl Stream {
+    /// Returns the amount of token streamed to the recipient by `timestamp`
+    pub fn accrued(&self, timestamp: u64) -> u128 {
+        let elapsed = timestamp.clamp(self.start_time, self.stop_time) - self.start_time;
+        (self.rate * (elapsed / 1000) as u128).min(self.deposit)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct FTStreamsState {
+    pub streams: HashMap<StreamId, Stream>,
+    pub next_stream_id: StreamId,
+}
+
+pub trait FTStreamsStateKeeper {
+    fn get(&self) -> &FTStreamsState;
+    fn get_mut(&mut self) -> &mut FTStreamsState;
+}
+
+/// Continuous per-second payments of the token
+///
+/// Deposits are held on the program's own balance until they are withdrawn or the stream is
+/// cancelled. Streams are fixed amounts of token, so they aren't available
+/// in the share-based mode where a rebase would change the value of locked deposits.
+pub trait FTStreams: FTCore + FTStreamsStateKeeper {
+    /// Creates a stream starting now and locks its deposit
+    ///
+    /// Requirements:
+    /// * `recipient` MUST be a non-zero address other than `msg::source()`
+    /// * `rate` MUST be non-zero
+    /// * `stop_time` MUST be at least one second in the future
+    /// * `msg::source()` MUST have enough tokens to cover `rate` for the whole stream
+    /// * The token MUST NOT be in the share-based mode
+    ///
+    /// Arguments:
+    /// * `recipient`: An account that will receive the stream
+    /// * `rate`: The amount of token streamed per second
+    /// * `stop_time`: The timestamp (in milliseconds) when the stream ends
+    fn create_stream(&mut self, recipient: &ActorId, rate: u128, stop_time: u64) {
+        if FTStateKeeper::get(self).total_shares.is_some() {
+            panic!("Streams are not available in the share-based mode");
+        }
+        let sender = msg::source();
+        if recipient == &ZERO_ID {
+            panic!("Zero addresses");
+        }
+        if recipient == &sender {
+            panic!("Stream to the sender");
+        }
+        if rate == 0 {
+            panic!("Zero stream rate");
+        }
+        let start_time = exec::block_timestamp();
+        let seconds = stop_time.saturating_sub(start_time) / 1000;
+        if seconds == 0 {
+            panic!("Stream must last at least one second");
+        }
+        let deposit = rate
+            .checked_mul(seconds as u128)
+            .expect("Stream deposit overflow");
+        FTStateKeeper::get_mut(self).transfer_balance(&sender, &exec::program_id(), deposit);
+
+        let stream_id = FTStreamsStateKeeper::get(self).next_stream_id;
+        FTStreamsStateKeeper::get_mut(self).next_stream_id += 1;
+        FTStreamsStateKeeper::get_mut(self).streams.insert(
+            stream_id,
+            Stream {
+                sender,
+                recipient: *recipient,
+                deposit,
+                rate,
+                start_time,
+                stop_time,
+                withdrawn: 0,
+            },
+        );
+        msg::reply(
+            FTStreamCreation {
+                stream_id,
+                sender,
+                recipient: *recipient,
+                deposit,
+                rate,
+                start_time,
+                stop_time,
+            },
+            0,
+        )
+        .expect("Error during a reply with FTEvent::FTStreamCreation");
+    }
+
+    /// Withdraws all the accrued token of the stream to its recipient
+    ///
+    /// Requirements:
+    /// * Only the stream recipient can call that action
+    ///
+    /// Arguments:
+    /// * `stream_id`: The ID of the stream
+    fn withdraw_from_stream(&mut self, stream_id: StreamId) {
+        let stream = self.stream(stream_id);
+        if stream.recipient != msg::source() {
+            panic!("Not the stream recipient");
+        }
+        let amount = stream.accrued(exec::block_timestamp()) - stream.withdrawn;
+        if amount == 0 {
+            panic!("Nothing to withdraw");
+        }
+        FTStateKeeper::get_mut(self).transfer_balance(
+            &exec::program_id(),
+            &stream.recipient,
+            amount,
+        );
+        if stream.withdrawn + amount == stream.deposit {
+            FTStreamsStateKeeper::get_mut(self)
+                .streams
+                .remove(&stream_id);
+        } else {
+            FTStreamsStateKeeper::get_mut(self)
+                .streams
+                .entry(stream_id)
+                .and_modify(|stream| stream.withdrawn += amount);
+        }
+        msg::reply(
+            FTStreamWithdrawal {
+                stream_id,
+                recipient: stream.recipient,
+                amount,
+            },
+            0,
+        )
+        .expect("Error during a reply with FTEvent::FTStreamWithdrawal");
+    }
+
+    /// Cancels the stream, paying the accrued token to the recipient
+    /// and returning the rest of the deposit to the sender
+    ///
+    /// Requirements:
+    /// * Only the stream sender or recipient can call that action
+    ///
+    /// Arguments:
+    /// * `stream_id`: The ID of the stream
+    fn cancel_stream(&mut self, stream_id: StreamId) {
+        let stream = self.stream(stream_id);
+        if stream.sender != msg::source() && stream.recipient != msg::source() {
+            panic!("Not allowed to cancel the stream");
+        }
+        let accrued = stream.accrued(exec::block_timestamp());
+        let recipient_amount = accrued - stream.withdrawn;
+        let sender_amount = stream.deposit - accrued;
+        let program_id = exec::program_id();
+        if recipient_amount != 0 {
+            FTStateKeeper::get_mut(self).transfer_balance(
+                &program_id,
+                &stream.recipient,
+                recipient_amount,
+            );
+        }
+        if sender_amount != 0 {
+            FTStateKeeper::get_mut(self).transfer_balance(
+                &program_id,
+                &stream.sender,
+                sender_amount,
+            );
+        }
+        FTStreamsStateKeeper::get_mut(self)
+            .streams
+            .remove(&stream_id);
+        msg::reply(
+            FTStreamCancellation {
+                stream_id,
+                recipient_amount,
+                sender_amount,
+            },
+            0,
+        )
+        .expect("Error during a reply with FTEvent::FTStreamCancellation");
+    }
+
+    fn stream(&self, stream_id: StreamId) -> Stream {
+        FTStreamsStateKeeper::get(self)
+            .streams
+            .get(&stream_id)
+            .cloned()
+            .expect("Stream does not exist")
+    }
+
+    /// Returns the amount of token `account` can currently get from the stream
+    fn stream_balance_of(&self, stream_id: StreamId, account: &ActorId) -> u128 {
+        let stream = self.stream(stream_id);
+        let accrued = stream.accrued(exec::block_timestamp());
+        if account == &stream.recipient {
+            accrued - stream.withdrawn
+        } else if account == &stream.sender {
+            stream.deposit - accrued
+        } else {
+            0
+        }
+    }
+}
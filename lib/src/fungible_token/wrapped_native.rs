@@ -0,0 +1,42 @@
+use crate::fungible_token::ft_core::*;
+use gstd::msg;
+
+/// Fungible token backed 1:1 by the native value attached to messages (WVARA-style)
+pub trait FTWrappedNative: FTCore {
+    /// Returns the existential deposit of the network, which is the smallest value
+    /// that can be sent along with a message
+    fn existential_deposit(&self) -> u128;
+
+    /// Mints tokens equal to the value attached to the message
+    ///
+    /// Requirements:
+    /// * `msg::value()` MUST be non-zero
+    fn deposit(&mut self) {
+        let value = msg::value();
+        if value == 0 {
+            panic!("Nothing to deposit");
+        }
+        self.mint(&msg::source(), value);
+    }
+
+    /// Burns `amount` of token and sends the same amount of value back to `msg::source()`
+    ///
+    /// If the value can't be sent, the whole action fails and the tokens stay on the balance.
+    ///
+    /// Requirements:
+    /// * `msg::source()` MUST have enough tokens on his balance
+    /// * `amount` MUST NOT be less than the existential deposit
+    ///
+    /// Arguments:
+    /// * `amount`: The amount of token to be unwrapped
+    fn withdraw(&mut self, amount: u128) {
+        if amount == 0 {
+            panic!("Nothing to withdraw");
+        }
+        if amount < self.existential_deposit() {
+            panic!("Amount is less than the existential deposit");
+        }
+        self.burn(amount);
+        msg::send_bytes(msg::source(), b"", amount).expect("Error during sending value");
+    }
+}
@@ -1,4 +1,7 @@
 #![no_std]
+#![cfg_attr(test, feature(test))]
 pub mod fungible_token;
+pub mod merkle;
+pub mod mint_phases;
 pub mod multitoken;
 pub mod non_fungible_token;
@@ -0,0 +1,97 @@
+use blake2::{digest::consts::U32, Blake2b, Digest};
+use gstd::ActorId;
+
+pub type Hash = [u8; 32];
+
+/// Returns the blake2b-256 hash of `data`
+pub fn hash(data: &[u8]) -> Hash {
+    Blake2b::<U32>::digest(data).into()
+}
+
+/// Returns the leaf of the account in an allowlist tree
+pub fn leaf(account: &ActorId) -> Hash {
+    hash(account.as_ref())
+}
+
+/// Returns the parent node of two nodes, which are hashed in ascending order
+/// so proofs don't need to store the side of each sibling
+pub fn node(a: &Hash, b: &Hash) -> Hash {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    let mut hasher = Blake2b::<U32>::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Checks that `leaf` is included in the tree with `root`
+///
+/// Arguments:
+/// * `proof`: the sibling nodes on the path from the leaf to the root
+/// * `root`: the root of the tree
+/// * `leaf`: the leaf to be checked
+pub fn verify(proof: &[Hash], root: &Hash, leaf: &Hash) -> bool {
+    &proof
+        .iter()
+        .fold(*leaf, |node_hash, sibling| node(&node_hash, sibling))
+        == root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // computed off-chain with `hashlib.blake2b(data, digest_size=32)`
+    const EMPTY_HASH: Hash = [
+        0x0e, 0x57, 0x51, 0xc0, 0x26, 0xe5, 0x43, 0xb2, 0xe8, 0xab, 0x2e, 0xb0, 0x60, 0x99, 0xda,
+        0xa1, 0xd1, 0xe5, 0xdf, 0x47, 0x77, 0x8f, 0x77, 0x87, 0xfa, 0xab, 0x45, 0xcd, 0xf1, 0x2f,
+        0xe3, 0xa8,
+    ];
+    const FIRST_LEAF: Hash = [
+        0xf4, 0x0c, 0xea, 0xf8, 0x6e, 0x57, 0x76, 0x92, 0x33, 0x32, 0xb8, 0xd8, 0xfd, 0x3b, 0xef,
+        0x84, 0x9c, 0xad, 0xb1, 0x9c, 0x69, 0x96, 0xbc, 0x27, 0x2a, 0xf1, 0xf6, 0x48, 0xd9, 0x56,
+        0x6a, 0x4c,
+    ];
+    /// The root of the tree with the leaves of accounts `[1; 32]`, `[2; 32]` and `[3; 32]`:
+    /// `node(node(leaf1, leaf2), leaf3)`.
+    const ROOT: Hash = [
+        0x89, 0x5e, 0xf4, 0x43, 0x0a, 0x72, 0x6f, 0x50, 0x3a, 0x97, 0x46, 0x37, 0x35, 0x40, 0x9b,
+        0xd6, 0x1a, 0x97, 0x72, 0x52, 0x0c, 0x9e, 0xfa, 0x51, 0x09, 0xb1, 0x0d, 0x10, 0xaf, 0xfd,
+        0x69, 0xa3,
+    ];
+
+    fn account(byte: u8) -> ActorId {
+        ActorId::from([byte; 32])
+    }
+
+    #[test]
+    fn hash_is_blake2b_256() {
+        assert_eq!(hash(b""), EMPTY_HASH);
+        assert_eq!(leaf(&account(1)), FIRST_LEAF);
+    }
+
+    #[test]
+    fn node_does_not_depend_on_order() {
+        let (a, b) = (leaf(&account(1)), leaf(&account(2)));
+        assert_eq!(node(&a, &b), node(&b, &a));
+    }
+
+    #[test]
+    fn verifies_proofs_against_known_root() {
+        let leaves = [1, 2, 3].map(|byte| leaf(&account(byte)));
+        let first_pair = node(&leaves[0], &leaves[1]);
+
+        assert!(verify(&[leaves[1], leaves[2]], &ROOT, &leaves[0]));
+        assert!(verify(&[leaves[0], leaves[2]], &ROOT, &leaves[1]));
+        assert!(verify(&[first_pair], &ROOT, &leaves[2]));
+    }
+
+    #[test]
+    fn rejects_invalid_proofs() {
+        let leaves = [1, 2, 3].map(|byte| leaf(&account(byte)));
+
+        assert!(!verify(&[leaves[1], leaves[2]], &ROOT, &leaf(&account(4))));
+        assert!(!verify(&[leaves[2], leaves[1]], &ROOT, &leaves[0]));
+        assert!(!verify(&[leaves[1]], &ROOT, &leaves[0]));
+        assert!(!verify(&[], &ROOT, &leaves[0]));
+    }
+}
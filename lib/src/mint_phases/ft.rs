@@ -0,0 +1,31 @@
+use crate::fungible_token::ft_core::*;
+use crate::merkle::Hash;
+use crate::mint_phases::state::*;
+use gstd::{msg, prelude::*};
+
+/// Minting of fungible tokens during time-windowed phases
+pub trait FTMintPhases: FTCore + MintPhasesStateKeeper {
+    /// Mints `amount` of token to `msg::source()` in the phase
+    ///
+    /// Requirements:
+    /// * The phase MUST be active
+    /// * `msg::source()` MUST be in the allowlist of the phase if it has one
+    /// * `amount` MUST NOT exceed the rest of the phase limit per address
+    /// * `msg::value()` MUST be equal to the phase price multiplied by `amount`
+    ///
+    /// Arguments:
+    /// * `phase_id`: The ID of the mint phase
+    /// * `proof`: The Merkle proof of `msg::source()` in the phase allowlist
+    /// * `amount`: The amount of token to be minted
+    fn phase_mint(&mut self, phase_id: PhaseId, proof: Vec<Hash>, amount: u128) {
+        if amount == 0 {
+            panic!("Nothing to mint");
+        }
+        let price =
+            MintPhasesStateKeeper::get_mut(self).claim(phase_id, &msg::source(), &proof, amount);
+        if msg::value() != price {
+            panic!("Attached value must be equal to the phase price");
+        }
+        self.mint(&msg::source(), amount);
+    }
+}
@@ -0,0 +1,3 @@
+pub mod ft;
+pub mod nft;
+pub mod state;
@@ -0,0 +1,32 @@
+use crate::merkle::Hash;
+use crate::mint_phases::state::*;
+use crate::non_fungible_token::{io::*, nft_core::*, token::*};
+use gstd::{msg, prelude::*};
+
+/// Minting of NFTs with the next free IDs during time-windowed phases
+pub trait NFTMintPhases: NFTCore + MintPhasesStateKeeper {
+    /// Mints a new token to `msg::source()` in the phase
+    ///
+    /// Requirements:
+    /// * The phase must be active
+    /// * `msg::source()` must be in the allowlist of the phase if it has one
+    /// * `msg::source()` must not have reached the phase limit per address
+    /// * `msg::value()` must be equal to the phase price
+    ///
+    /// Arguments:
+    /// * `phase_id`: the ID of the mint phase
+    /// * `proof`: the Merkle proof of `msg::source()` in the phase allowlist
+    /// * `token_metadata`: optional additional metadata about NFT
+    fn phase_mint(
+        &mut self,
+        phase_id: PhaseId,
+        proof: Vec<Hash>,
+        token_metadata: Option<TokenMetadata>,
+    ) -> NFTTransfer {
+        let price = MintPhasesStateKeeper::get_mut(self).claim(phase_id, &msg::source(), &proof, 1);
+        if msg::value() != price {
+            panic!("NonFungibleToken: Attached value must be equal to the phase price");
+        }
+        self.mint_next(&msg::source(), token_metadata)
+    }
+}
@@ -0,0 +1,81 @@
+use crate::merkle::{self, Hash};
+use gstd::{exec, prelude::*, ActorId};
+use hashbrown::HashMap;
+
+pub type PhaseId = u32;
+
+#[derive(Default, Debug, Encode, Decode, PartialEq, Eq, Clone, Copy, TypeInfo)]
+pub struct MintPhase {
+    /// Phase start timestamp (in milliseconds).
+    pub start_time: u64,
+    /// Phase end timestamp (in milliseconds).
+    pub end_time: u64,
+    /// Price of one token in the native value.
+    pub price: u128,
+    /// Root of the allowlist tree, anyone can mint if `None`.
+    pub merkle_root: Option<Hash>,
+    /// Maximum amount of tokens minted by one account, unlimited if `None`.
+    pub max_per_address: Option<u128>,
+}
+
+#[derive(Debug, Default)]
+pub struct MintPhasesState {
+    pub phases: HashMap<PhaseId, MintPhase>,
+    pub claimed: HashMap<(PhaseId, ActorId), u128>,
+}
+
+impl MintPhasesState {
+    /// Records `amount` of tokens minted by `account` in the phase and returns their price
+    ///
+    /// Requirements:
+    /// * The phase must be active
+    /// * `account` must be in the allowlist of the phase if it has one
+    /// * `amount` must not exceed the rest of the account quota
+    pub fn claim(
+        &mut self,
+        phase_id: PhaseId,
+        account: &ActorId,
+        proof: &[Hash],
+        amount: u128,
+    ) -> u128 {
+        let phase = *self
+            .phases
+            .get(&phase_id)
+            .expect("Mint phase does not exist");
+        let now = exec::block_timestamp();
+        if now < phase.start_time || now >= phase.end_time {
+            panic!("Mint phase is not active");
+        }
+        if let Some(root) = phase.merkle_root {
+            if !merkle::verify(proof, &root, &merkle::leaf(account)) {
+                panic!("Account is not in the allowlist");
+            }
+        }
+        let claimed = self.claimed.entry((phase_id, *account)).or_default();
+        let total = claimed
+            .checked_add(amount)
+            .expect("Mint phase claim overflow");
+        if let Some(max_per_address) = phase.max_per_address {
+            if total > max_per_address {
+                panic!("Mint phase limit per address is exceeded");
+            }
+        }
+        *claimed = total;
+        phase
+            .price
+            .checked_mul(amount)
+            .expect("Mint phase price overflow")
+    }
+
+    pub fn claimed(&self, phase_id: PhaseId, account: &ActorId) -> u128 {
+        self.claimed
+            .get(&(phase_id, *account))
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+pub trait MintPhasesStateKeeper {
+    fn get(&self) -> &MintPhasesState;
+    fn get_mut(&mut self) -> &mut MintPhasesState;
+}
@@ -28,6 +28,8 @@ pub enum MTKQuery {
     MetadataOf(TokenId),
     URI(TokenId),
     TokensForOwner(ActorId),
+    /// Owner, offset and limit.
+    TokensForOwnerPage(ActorId, u32, u32),
     TokensIDsForOwner(ActorId),
     Supply(TokenId),
     OwnerOf(TokenId),
@@ -42,6 +44,8 @@ pub enum MTKQueryReply {
     URI(String),
     MetadataOf(TokenMetadata),
     TokensForOwner(Vec<Token>),
+    /// Tokens of the page and the total number of tokens of the owner.
+    TokensForOwnerPage(Vec<Token>, u32),
     TokensIDsForOwner(Vec<TokenId>),
     Supply(u128),
     OwnerOf(ActorId),
@@ -103,6 +107,24 @@ pub trait MTKTokenState: StateKeeper {
         tokens
     }
 
+    /// Returns `limit` tokens of `owner` starting from `offset` ordered by ID
+    /// and the total number of tokens of `owner`
+    fn tokens_for_owner_page(&self, owner: &ActorId, offset: u32, limit: u32) -> (Vec<Token>, u32) {
+        let mut token_ids = Self::tokens_ids_for_owner(self, owner);
+        token_ids.sort_unstable();
+        let tokens = token_ids
+            .iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .map(|id| Token {
+                id: *id,
+                amount: Self::get_balance(self, owner, id),
+                metadata: Some(Self::get_metadata(self, *id)),
+            })
+            .collect();
+        (tokens, token_ids.len() as u32)
+    }
+
     fn tokens_ids_for_owner(&self, owner: &ActorId) -> Vec<TokenId> {
         let mut tokens: Vec<TokenId> = Vec::new();
         let balances = &self.get().balances;
@@ -143,6 +165,10 @@ pub trait MTKTokenState: StateKeeper {
             MTKQuery::TokensForOwner(owner) => {
                 MTKQueryReply::TokensForOwner(Self::tokens_for_owner(self, &owner))
             }
+            MTKQuery::TokensForOwnerPage(owner, offset, limit) => {
+                let (tokens, total) = Self::tokens_for_owner_page(self, &owner, offset, limit);
+                MTKQueryReply::TokensForOwnerPage(tokens, total)
+            }
             MTKQuery::Supply(id) => MTKQueryReply::Supply(Self::supply(self, id)),
             MTKQuery::OwnerOf(id) => MTKQueryReply::OwnerOf(Self::owner_of(self, id)),
         };
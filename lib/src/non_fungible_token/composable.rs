@@ -0,0 +1,207 @@
+use crate::non_fungible_token::{io::*, nft_core::*, token::*};
+use gstd::{prelude::*, ActorId};
+
+/// Fungible balances owned by a token: (contract, token ID within the contract) to amount.
+/// The token ID is `0` for fungible token contracts.
+pub type ChildBalances = BTreeMap<(ActorId, u128), u128>;
+
+#[derive(Default, Debug, Encode, Decode, PartialEq, Eq, PartialOrd, Ord, Clone, TypeInfo, Hash)]
+pub struct TokenTree {
+    pub token_id: TokenId,
+    pub children: Vec<TokenTree>,
+    pub balances: ChildBalances,
+}
+
+/// Tokens owning other tokens of the collection and balances of other contracts
+///
+/// Nested tokens always belong to the owner of the root token, follow it on transfer
+/// and can't be transferred or burnt until they are detached.
+pub trait NFTComposable: NFTCore {
+    /// Nests a token into another token
+    ///
+    /// Requirements:
+    /// * Only the child owner or approved account can call that action
+    /// * `parent_id` and `child_id` must be the IDs of existing NFTs
+    /// * `child_id` must be transferable and not attached to another parent
+    /// * `parent_id` must not be nested into `child_id`
+    ///
+    /// Arguments:
+    /// * `parent_id`: the ID of the NFT that will own the child
+    /// * `child_id`: the ID of the NFT that will be nested
+    fn attach_child(&mut self, parent_id: TokenId, child_id: TokenId) -> NFTChildAttach {
+        let parent_owner = self.owner_of(parent_id);
+        let child_owner = self.owner_of(child_id);
+        self.assert_transferable(child_id);
+        self.assert_not_attached(child_id);
+        self.assert_not_locked(child_id);
+        self.assert_can_transfer(child_id, &child_owner);
+        if self.get().root_of(parent_id) == child_id {
+            panic!("NonFungibleToken: Token can't be nested into itself");
+        }
+        if child_owner != parent_owner {
+            self.move_token(&child_owner, &parent_owner, child_id);
+            for descendant in self.get().descendants_of(child_id) {
+                self.move_token(&child_owner, &parent_owner, descendant);
+            }
+        }
+        self.get_mut().parent_of.insert(child_id, parent_id);
+        self.get_mut()
+            .children_of
+            .entry(parent_id)
+            .or_default()
+            .insert(child_id);
+        NFTChildAttach {
+            parent_id,
+            child_id,
+        }
+    }
+
+    /// Takes a nested token out of its parent and transfers it to `to`
+    ///
+    /// Requirements:
+    /// * Only the owner or approved account of the root token can call that action
+    /// * `child_id` must be the ID of the NFT attached to a parent
+    /// * Neither the child, its descendants nor its ancestors can be locked
+    /// * `to` must be a non-zero account
+    ///
+    /// Arguments:
+    /// * `child_id`: the ID of the nested NFT
+    /// * `to`: An account to which the NFT will be transferred
+    fn detach_child(&mut self, child_id: TokenId, to: &ActorId) -> NFTChildDetach {
+        let parent_id = *self
+            .get()
+            .parent_of
+            .get(&child_id)
+            .expect("NonFungibleToken: Token is not attached to a parent");
+        self.assert_zero_address(to);
+        let root_id = self.get().root_of(parent_id);
+        let owner = self.owner_of(root_id);
+        self.assert_can_transfer(root_id, &owner);
+        self.assert_not_locked(child_id);
+        self.assert_ancestors_not_locked(parent_id);
+
+        self.get_mut().parent_of.remove(&child_id);
+        if let Some(children) = self.get_mut().children_of.get_mut(&parent_id) {
+            children.remove(&child_id);
+            if children.is_empty() {
+                self.get_mut().children_of.remove(&parent_id);
+            }
+        }
+        if &owner != to {
+            self.move_token(&owner, to, child_id);
+            for descendant in self.get().descendants_of(child_id) {
+                self.move_token(&owner, to, descendant);
+            }
+        }
+        NFTChildDetach {
+            parent_id,
+            child_id,
+            to: *to,
+        }
+    }
+
+    /// Records a fungible balance of another contract as owned by the token
+    ///
+    /// The balance isn't pulled from the contract here and is paid out by `detach_balance`,
+    /// so the program must check that `msg::source()` is allowed to call that action and that
+    /// the program has already received the tokens on its own balance in the contract.
+    ///
+    /// Arguments:
+    /// * `token_id`: the ID of the NFT that will own the balance
+    /// * `contract`: the FT or MTK contract
+    /// * `id`: the token ID within the MTK contract or `0` for FT
+    /// * `amount`: the amount of tokens
+    fn attach_balance(
+        &mut self,
+        token_id: TokenId,
+        contract: &ActorId,
+        id: u128,
+        amount: u128,
+    ) -> NFTBalanceAttach {
+        // the token must exist
+        self.owner_of(token_id);
+        let balance = self
+            .get_mut()
+            .child_balances
+            .entry(token_id)
+            .or_default()
+            .entry((*contract, id))
+            .or_default();
+        *balance = balance
+            .checked_add(amount)
+            .expect("NonFungibleToken: Balance overflow");
+        NFTBalanceAttach {
+            token_id,
+            contract: *contract,
+            id,
+            amount,
+        }
+    }
+
+    /// Removes a fungible balance of another contract from the token,
+    /// after that the program must send the tokens to `to` in the contract
+    ///
+    /// Requirements:
+    /// * Only the owner or approved account of the root token can call that action
+    /// * The token must own at least `amount` of the balance
+    /// * Neither the token nor its ancestors can be locked
+    /// * `to` must be a non-zero account
+    ///
+    /// Arguments:
+    /// * `token_id`: the ID of the NFT owning the balance
+    /// * `contract`: the FT or MTK contract
+    /// * `id`: the token ID within the MTK contract or `0` for FT
+    /// * `amount`: the amount of tokens
+    /// * `to`: An account to which the tokens will be sent
+    fn detach_balance(
+        &mut self,
+        token_id: TokenId,
+        contract: &ActorId,
+        id: u128,
+        amount: u128,
+        to: &ActorId,
+    ) -> NFTBalanceDetach {
+        self.assert_zero_address(to);
+        let root_id = self.get().root_of(token_id);
+        let owner = self.owner_of(root_id);
+        self.assert_can_transfer(root_id, &owner);
+        self.assert_ancestors_not_locked(token_id);
+
+        let balances = self
+            .get_mut()
+            .child_balances
+            .get_mut(&token_id)
+            .expect("NonFungibleToken: Token has no balances");
+        let balance = balances
+            .get_mut(&(*contract, id))
+            .expect("NonFungibleToken: Token has no balance in the contract");
+        if *balance < amount {
+            panic!("NonFungibleToken: Amount exceeds token's balance");
+        }
+        *balance -= amount;
+        if *balance == 0 {
+            balances.remove(&(*contract, id));
+        }
+        if balances.is_empty() {
+            self.get_mut().child_balances.remove(&token_id);
+        }
+        NFTBalanceDetach {
+            token_id,
+            contract: *contract,
+            id,
+            amount,
+            to: *to,
+        }
+    }
+
+    /// Checks that neither the token nor the tokens it's nested into are locked
+    fn assert_ancestors_not_locked(&self, token_id: TokenId) {
+        let mut ancestor = Some(token_id);
+        while let Some(id) = ancestor {
+            if self.get().is_locked(id) {
+                panic!("NonFungibleToken: Token is locked");
+            }
+            ancestor = self.get().parent_of.get(&id).copied();
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::non_fungible_token::{royalties::*, token::*};
+use crate::non_fungible_token::{marketplace::*, royalties::*, token::*};
 use gstd::{prelude::*, ActorId};
 
 #[derive(
@@ -10,6 +10,15 @@ pub struct NFTTransfer {
     pub token_id: TokenId,
 }
 
+/// Transfer of several tokens at once (ERC-2309 style)
+#[derive(Default, Debug, Encode, Decode, PartialEq, Eq, PartialOrd, Ord, Clone, TypeInfo, Hash)]
+pub struct NFTConsecutiveTransfer {
+    pub from: ActorId,
+    pub to: ActorId,
+    /// Inclusive ranges of consecutive token IDs in ascending order.
+    pub ranges: Vec<(TokenId, TokenId)>,
+}
+
 #[derive(Default, Debug, Encode, Decode, PartialEq, Eq, PartialOrd, Ord, Clone, TypeInfo, Hash)]
 pub struct NFTTransferPayout {
     pub from: ActorId,
@@ -26,3 +35,161 @@ pub struct NFTApproval {
     pub approved_account: ActorId,
     pub token_id: TokenId,
 }
+
+#[derive(
+    Default, Debug, Encode, Decode, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, TypeInfo, Hash,
+)]
+pub struct NFTApprovalForAll {
+    pub owner: ActorId,
+    pub operator: ActorId,
+    pub approved: bool,
+}
+
+#[derive(
+    Default, Debug, Encode, Decode, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, TypeInfo, Hash,
+)]
+pub struct NFTUpdateUser {
+    pub token_id: TokenId,
+    pub user: ActorId,
+    pub expires: u64,
+}
+
+#[derive(
+    Default, Debug, Encode, Decode, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, TypeInfo, Hash,
+)]
+pub struct NFTChildAttach {
+    pub parent_id: TokenId,
+    pub child_id: TokenId,
+}
+
+#[derive(
+    Default, Debug, Encode, Decode, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, TypeInfo, Hash,
+)]
+pub struct NFTChildDetach {
+    pub parent_id: TokenId,
+    pub child_id: TokenId,
+    pub to: ActorId,
+}
+
+#[derive(
+    Default, Debug, Encode, Decode, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, TypeInfo, Hash,
+)]
+pub struct NFTBalanceAttach {
+    pub token_id: TokenId,
+    pub contract: ActorId,
+    pub id: u128,
+    pub amount: u128,
+}
+
+#[derive(
+    Default, Debug, Encode, Decode, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, TypeInfo, Hash,
+)]
+pub struct NFTBalanceDetach {
+    pub token_id: TokenId,
+    pub contract: ActorId,
+    pub id: u128,
+    pub amount: u128,
+    pub to: ActorId,
+}
+
+#[derive(Default, Debug, Encode, Decode, PartialEq, Eq, PartialOrd, Ord, Clone, TypeInfo, Hash)]
+pub struct NFTAttributeUpdate {
+    pub token_id: TokenId,
+    pub trait_type: String,
+    /// `None` if the attribute was removed.
+    pub attribute: Option<TokenAttribute>,
+}
+
+#[derive(
+    Default, Debug, Encode, Decode, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, TypeInfo, Hash,
+)]
+pub struct NFTMetadataUpdate {
+    /// `None` if the metadata of all tokens was updated.
+    pub token_id: Option<TokenId>,
+}
+
+#[derive(
+    Default, Debug, Encode, Decode, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, TypeInfo, Hash,
+)]
+pub struct NFTMetadataFreeze {
+    /// `None` if the metadata of all tokens was frozen.
+    pub token_id: Option<TokenId>,
+}
+
+#[derive(
+    Default, Debug, Encode, Decode, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, TypeInfo, Hash,
+)]
+pub struct NFTMetadataCommit {
+    pub token_id: TokenId,
+    pub commitment: [u8; 32],
+}
+
+#[derive(Default, Debug, Encode, Decode, PartialEq, Eq, PartialOrd, Ord, Clone, TypeInfo, Hash)]
+pub struct NFTRoyaltiesUpdate {
+    /// `None` if the collection royalties were updated.
+    pub token_id: Option<TokenId>,
+    pub royalties: Option<Royalties>,
+}
+
+#[derive(Debug, Encode, Decode, PartialEq, Eq, Clone, Copy, TypeInfo)]
+pub struct NFTListing {
+    pub token_id: TokenId,
+    pub listing: Listing,
+}
+
+#[derive(
+    Default, Debug, Encode, Decode, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, TypeInfo, Hash,
+)]
+pub struct NFTListingCancel {
+    pub token_id: TokenId,
+}
+
+#[derive(
+    Default, Debug, Encode, Decode, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, TypeInfo, Hash,
+)]
+pub struct NFTBid {
+    pub token_id: TokenId,
+    pub bidder: ActorId,
+    pub amount: u128,
+}
+
+#[derive(Default, Debug, Encode, Decode, PartialEq, Eq, Clone, Copy, TypeInfo)]
+pub struct NFTOffer {
+    pub token_id: TokenId,
+    pub buyer: ActorId,
+    pub offer: Offer,
+}
+
+#[derive(
+    Default, Debug, Encode, Decode, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, TypeInfo, Hash,
+)]
+pub struct NFTOfferWithdrawal {
+    pub token_id: TokenId,
+    pub buyer: ActorId,
+}
+
+#[derive(Debug, Encode, Decode, PartialEq, Eq, Clone, TypeInfo)]
+pub struct NFTSale {
+    pub token_id: TokenId,
+    pub seller: ActorId,
+    pub buyer: ActorId,
+    pub currency: Currency,
+    pub price: u128,
+    pub payouts: Payout,
+}
+
+#[derive(
+    Default, Debug, Encode, Decode, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, TypeInfo, Hash,
+)]
+pub struct NFTLocked {
+    pub token_id: TokenId,
+    pub locker: ActorId,
+    pub until: u64,
+}
+
+#[derive(
+    Default, Debug, Encode, Decode, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, TypeInfo, Hash,
+)]
+pub struct NFTUnlocked {
+    pub token_id: TokenId,
+}
@@ -0,0 +1,72 @@
+use crate::non_fungible_token::{io::*, nft_core::*, token::*};
+use gstd::{exec, msg, prelude::*, ActorId};
+
+#[derive(
+    Default, Debug, Encode, Decode, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, TypeInfo, Hash,
+)]
+pub struct TokenLock {
+    /// An account able to unlock the token before the lock expires.
+    pub locker: ActorId,
+    /// The timestamp (in milliseconds) until which the token is locked.
+    pub until: u64,
+}
+
+/// Locking (staking) tokens in place: a locked token stays with its owner,
+/// but can't be transferred, approved or burnt until it's unlocked or the lock expires
+pub trait NFTLockable: NFTCore {
+    /// Locks the token until `until`
+    ///
+    /// Requirements:
+    /// * Only the token owner or approved account can call that action
+    /// * `token_id` must be the ID of the existing NFT which isn't locked
+    /// * `locker` must be a non-zero account
+    /// * `until` must be in the future
+    ///
+    /// Arguments:
+    /// * `token_id`: the ID of the NFT
+    /// * `until`: the timestamp (in milliseconds) until which the NFT is locked
+    /// * `locker`: An account that will be able to unlock the NFT
+    fn lock(&mut self, token_id: TokenId, until: u64, locker: &ActorId) -> NFTLocked {
+        let owner = self.owner_of(token_id);
+        self.assert_can_transfer(token_id, &owner);
+        self.assert_zero_address(locker);
+        if self.get().is_locked(token_id) {
+            panic!("NonFungibleToken: Token is locked");
+        }
+        if until <= exec::block_timestamp() {
+            panic!("NonFungibleToken: Lock expiration is in the past");
+        }
+        self.get_mut().locks.insert(
+            token_id,
+            TokenLock {
+                locker: *locker,
+                until,
+            },
+        );
+        NFTLocked {
+            token_id,
+            locker: *locker,
+            until,
+        }
+    }
+
+    /// Unlocks the token before the lock expires
+    ///
+    /// Requirements:
+    /// * Only the locker can call that action
+    /// * `token_id` must be the ID of the locked NFT
+    ///
+    /// Arguments:
+    /// * `token_id`: the ID of the NFT
+    fn unlock(&mut self, token_id: TokenId) -> NFTUnlocked {
+        if !self.get().is_locked(token_id) {
+            panic!("NonFungibleToken: Token is not locked");
+        }
+        let lock = self.get().locks[&token_id];
+        if lock.locker != msg::source() {
+            panic!("NonFungibleToken: Only the locker can unlock the token");
+        }
+        self.get_mut().locks.remove(&token_id);
+        NFTUnlocked { token_id }
+    }
+}
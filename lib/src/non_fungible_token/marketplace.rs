@@ -0,0 +1,454 @@
+use crate::non_fungible_token::{io::*, nft_core::*, state::*, token::*};
+use gstd::{exec, msg, prelude::*, ActorId};
+use hashbrown::HashMap;
+use primitive_types::U256;
+
+#[derive(Default, Debug, Encode, Decode, PartialEq, Eq, Clone, Copy, TypeInfo)]
+pub enum Currency {
+    /// The native value attached to messages.
+    #[default]
+    Native,
+    /// The fungible token kept in the state of the program itself (not an external FT contract),
+    /// see `NFTMarketplace::transfer_token`.
+    Token,
+}
+
+#[derive(Debug, Encode, Decode, PartialEq, Eq, Clone, Copy, TypeInfo)]
+pub enum SaleKind {
+    FixedPrice {
+        price: u128,
+    },
+    EnglishAuction {
+        reserve_price: u128,
+        /// The bidder and the amount of the highest bid.
+        highest_bid: Option<(ActorId, u128)>,
+    },
+    /// The price decreases linearly from `start_price` to `end_price` during the listing.
+    DutchAuction {
+        start_price: u128,
+        end_price: u128,
+    },
+}
+
+#[derive(Debug, Encode, Decode, PartialEq, Eq, Clone, Copy, TypeInfo)]
+pub struct Listing {
+    pub seller: ActorId,
+    pub currency: Currency,
+    pub kind: SaleKind,
+    /// Listing start timestamp (in milliseconds).
+    pub start_time: u64,
+    /// Listing end timestamp (in milliseconds).
+    pub end_time: u64,
+}
+
+impl Listing {
+    /// Returns the price of the fixed-price listing or the Dutch auction at `timestamp`
+    pub fn price(&self, timestamp: u64) -> Option<u128> {
+        match self.kind {
+            SaleKind::FixedPrice { price } => Some(price),
+            SaleKind::DutchAuction {
+                start_price,
+                end_price,
+            } => {
+                let duration = self.end_time - self.start_time;
+                let elapsed = timestamp.clamp(self.start_time, self.end_time) - self.start_time;
+                let discount = U256::from(start_price - end_price) * U256::from(elapsed)
+                    / U256::from(duration);
+                // the discount can't exceed `start_price - end_price`
+                Some(start_price - discount.as_u128())
+            }
+            SaleKind::EnglishAuction { .. } => None,
+        }
+    }
+}
+
+#[derive(Default, Debug, Encode, Decode, PartialEq, Eq, Clone, Copy, TypeInfo)]
+pub struct Offer {
+    pub currency: Currency,
+    pub amount: u128,
+    /// Timestamp (in milliseconds) after which the offer can't be accepted.
+    pub expires_at: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct NFTMarketplaceState {
+    pub listings: HashMap<TokenId, Listing>,
+    /// Offers by token and buyer, their payments are held by the program.
+    pub offers: HashMap<(TokenId, ActorId), Offer>,
+}
+
+pub trait NFTMarketplaceStateKeeper {
+    fn get(&self) -> &NFTMarketplaceState;
+    fn get_mut(&mut self) -> &mut NFTMarketplaceState;
+}
+
+/// Sales of the tokens of the collection
+///
+/// Listed tokens and payments of bids and offers are held by the program
+/// until a sale is completed or cancelled. Sale proceeds are split by `NFTCore::nft_payout`.
+/// Payments are made either in the native value or in the program's own fungible token,
+/// tokens of external FT contracts aren't supported.
+pub trait NFTMarketplace: NFTCore + NFTMarketplaceStateKeeper {
+    /// Transfers `amount` of the program's own fungible token used as `Currency::Token`
+    ///
+    /// The transfer is synchronous, so it can only move balances in the state of the program:
+    /// programs implementing `FTCore` should call `FTState::transfer_balance`.
+    /// The default implementation doesn't support token payments.
+    fn transfer_token(&mut self, _from: &ActorId, _to: &ActorId, _amount: u128) {
+        panic!("NonFungibleToken: Token payments are not supported");
+    }
+
+    /// Lists the token for sale and takes it into escrow
+    ///
+    /// Requirements:
+    /// * Only the token owner or approved account can call that action
+    /// * `token_id` must be the ID of the existing non-soulbound unlocked NFT
+    ///   not attached to a parent
+    /// * The Dutch auction's `start_price` must not be less than `end_price`
+    /// * `duration` must be non-zero
+    ///
+    /// Arguments:
+    /// * `token_id`: the ID of the NFT
+    /// * `currency`: the currency of the payment
+    /// * `kind`: the kind of the sale
+    /// * `duration`: the duration of the listing (in milliseconds)
+    fn list(
+        &mut self,
+        token_id: TokenId,
+        currency: Currency,
+        kind: SaleKind,
+        duration: u64,
+    ) -> NFTListing {
+        if duration == 0 {
+            panic!("NonFungibleToken: Zero listing duration");
+        }
+        let kind = match kind {
+            SaleKind::DutchAuction {
+                start_price,
+                end_price,
+            } if start_price < end_price => {
+                panic!("NonFungibleToken: Dutch auction price must decrease")
+            }
+            SaleKind::EnglishAuction { reserve_price, .. } => SaleKind::EnglishAuction {
+                reserve_price,
+                highest_bid: None,
+            },
+            kind => kind,
+        };
+        let seller = self.internal_transfer(&exec::program_id(), token_id);
+        let start_time = exec::block_timestamp();
+        let listing = Listing {
+            seller,
+            currency,
+            kind,
+            start_time,
+            end_time: start_time.saturating_add(duration),
+        };
+        NFTMarketplaceStateKeeper::get_mut(self)
+            .listings
+            .insert(token_id, listing);
+        NFTListing { token_id, listing }
+    }
+
+    /// Cancels the listing and returns the token to the seller
+    ///
+    /// Requirements:
+    /// * Only the seller can call that action
+    /// * The English auction must have no bids
+    ///
+    /// Arguments:
+    /// * `token_id`: the ID of the listed NFT
+    fn cancel_listing(&mut self, token_id: TokenId) -> NFTListingCancel {
+        let listing = self.listing(token_id);
+        if listing.seller != msg::source() {
+            panic!("NonFungibleToken: Only the seller can cancel the listing");
+        }
+        if let SaleKind::EnglishAuction {
+            highest_bid: Some(_),
+            ..
+        } = listing.kind
+        {
+            panic!("NonFungibleToken: Auction already has bids");
+        }
+        NFTMarketplaceStateKeeper::get_mut(self)
+            .listings
+            .remove(&token_id);
+        self.release_token(&listing.seller, token_id);
+        NFTListingCancel { token_id }
+    }
+
+    /// Buys the token of the fixed-price listing or the Dutch auction at its current price
+    ///
+    /// Requirements:
+    /// * The listing must be active
+    /// * `msg::value()` must cover the price if the currency is native,
+    ///   otherwise `msg::source()` must have enough tokens of the program
+    ///
+    /// The value attached above the price is returned to the buyer, since the price
+    /// of the Dutch auction at the moment of execution isn't known in advance.
+    ///
+    /// Arguments:
+    /// * `token_id`: the ID of the listed NFT
+    fn buy(&mut self, token_id: TokenId) -> NFTSale {
+        let listing = self.listing(token_id);
+        let now = exec::block_timestamp();
+        if now >= listing.end_time {
+            panic!("NonFungibleToken: Listing has expired");
+        }
+        let price = listing
+            .price(now)
+            .expect("NonFungibleToken: Auction token can't be bought directly");
+        let buyer = msg::source();
+        if listing.currency == Currency::Native {
+            let value = msg::value();
+            if value < price {
+                panic!("NonFungibleToken: Attached value is less than the price");
+            }
+            self.pay(Currency::Native, &buyer, value - price);
+        } else {
+            self.take_payment(listing.currency, &buyer, price);
+        }
+        NFTMarketplaceStateKeeper::get_mut(self)
+            .listings
+            .remove(&token_id);
+        self.complete_sale(token_id, &listing.seller, &buyer, listing.currency, price)
+    }
+
+    /// Places a bid in the English auction, returning the previous bid to its bidder
+    ///
+    /// Requirements:
+    /// * The auction must be active
+    /// * `amount` must not be less than the reserve price and must be higher than the current bid
+    /// * `msg::value()` must be equal to `amount` if the currency is native,
+    ///   otherwise `msg::source()` must have enough tokens of the program
+    ///
+    /// Arguments:
+    /// * `token_id`: the ID of the auctioned NFT
+    /// * `amount`: the amount of the bid
+    fn bid(&mut self, token_id: TokenId, amount: u128) -> NFTBid {
+        let listing = self.listing(token_id);
+        if exec::block_timestamp() >= listing.end_time {
+            panic!("NonFungibleToken: Auction has ended");
+        }
+        let SaleKind::EnglishAuction {
+            reserve_price,
+            highest_bid,
+        } = listing.kind
+        else {
+            panic!("NonFungibleToken: Token is not auctioned");
+        };
+        if amount == 0 || amount < reserve_price {
+            panic!("NonFungibleToken: Bid is less than the reserve price");
+        }
+        if let Some((_, highest_amount)) = highest_bid {
+            if amount <= highest_amount {
+                panic!("NonFungibleToken: Bid must be higher than the current one");
+            }
+        }
+        let bidder = msg::source();
+        self.take_payment(listing.currency, &bidder, amount);
+        if let Some((previous_bidder, previous_amount)) = highest_bid {
+            self.pay(listing.currency, &previous_bidder, previous_amount);
+        }
+        NFTMarketplaceStateKeeper::get_mut(self)
+            .listings
+            .entry(token_id)
+            .and_modify(|listing| {
+                listing.kind = SaleKind::EnglishAuction {
+                    reserve_price,
+                    highest_bid: Some((bidder, amount)),
+                }
+            });
+        NFTBid {
+            token_id,
+            bidder,
+            amount,
+        }
+    }
+
+    /// Settles the ended English auction: sells the token to the highest bidder
+    /// or returns it to the seller if there are no bids
+    ///
+    /// Arguments:
+    /// * `token_id`: the ID of the auctioned NFT
+    fn settle_auction(&mut self, token_id: TokenId) -> Option<NFTSale> {
+        let listing = self.listing(token_id);
+        let SaleKind::EnglishAuction { highest_bid, .. } = listing.kind else {
+            panic!("NonFungibleToken: Token is not auctioned");
+        };
+        if exec::block_timestamp() < listing.end_time {
+            panic!("NonFungibleToken: Auction has not ended");
+        }
+        NFTMarketplaceStateKeeper::get_mut(self)
+            .listings
+            .remove(&token_id);
+        if let Some((bidder, amount)) = highest_bid {
+            Some(self.complete_sale(token_id, &listing.seller, &bidder, listing.currency, amount))
+        } else {
+            self.release_token(&listing.seller, token_id);
+            None
+        }
+    }
+
+    /// Makes an offer for the token, replacing the previous offer of `msg::source()`
+    ///
+    /// Requirements:
+    /// * `token_id` must be the ID of the existing NFT
+    /// * `amount` must be non-zero and `expires_at` must be in the future
+    /// * `msg::value()` must be equal to `amount` if the currency is native,
+    ///   otherwise `msg::source()` must have enough tokens of the program
+    ///
+    /// Arguments:
+    /// * `token_id`: the ID of the NFT
+    /// * `currency`: the currency of the payment
+    /// * `amount`: the offered price
+    /// * `expires_at`: the timestamp (in milliseconds) after which the offer can't be accepted
+    fn make_offer(
+        &mut self,
+        token_id: TokenId,
+        currency: Currency,
+        amount: u128,
+        expires_at: u64,
+    ) -> NFTOffer {
+        // the token must exist
+        self.owner_of(token_id);
+        if amount == 0 {
+            panic!("NonFungibleToken: Zero offer");
+        }
+        if expires_at <= exec::block_timestamp() {
+            panic!("NonFungibleToken: Offer expiration must be in the future");
+        }
+        let buyer = msg::source();
+        self.take_payment(currency, &buyer, amount);
+        let offer = Offer {
+            currency,
+            amount,
+            expires_at,
+        };
+        if let Some(previous) = NFTMarketplaceStateKeeper::get_mut(self)
+            .offers
+            .insert((token_id, buyer), offer)
+        {
+            self.pay(previous.currency, &buyer, previous.amount);
+        }
+        NFTOffer {
+            token_id,
+            buyer,
+            offer,
+        }
+    }
+
+    /// Withdraws the offer of `msg::source()` and returns its payment
+    ///
+    /// Arguments:
+    /// * `token_id`: the ID of the NFT
+    fn withdraw_offer(&mut self, token_id: TokenId) -> NFTOfferWithdrawal {
+        let buyer = msg::source();
+        let offer = NFTMarketplaceStateKeeper::get_mut(self)
+            .offers
+            .remove(&(token_id, buyer))
+            .expect("NonFungibleToken: Offer does not exist");
+        self.pay(offer.currency, &buyer, offer.amount);
+        NFTOfferWithdrawal { token_id, buyer }
+    }
+
+    /// Accepts the offer, transferring the token to the buyer
+    ///
+    /// Requirements:
+    /// * Only the token owner or approved account can call that action
+    /// * The token must not be listed
+    /// * The offer must not be expired
+    ///
+    /// Arguments:
+    /// * `token_id`: the ID of the NFT
+    /// * `buyer`: the account that made the offer
+    fn accept_offer(&mut self, token_id: TokenId, buyer: &ActorId) -> NFTSale {
+        let offer = NFTMarketplaceStateKeeper::get_mut(self)
+            .offers
+            .remove(&(token_id, *buyer))
+            .expect("NonFungibleToken: Offer does not exist");
+        if offer.expires_at <= exec::block_timestamp() {
+            panic!("NonFungibleToken: Offer has expired");
+        }
+        let program_id = exec::program_id();
+        let seller = self.owner_of(token_id);
+        // listed tokens are held by the program
+        if seller == program_id {
+            panic!("NonFungibleToken: Token is listed");
+        }
+        self.internal_transfer(&program_id, token_id);
+        self.complete_sale(token_id, &seller, buyer, offer.currency, offer.amount)
+    }
+
+    fn listing(&self, token_id: TokenId) -> Listing {
+        *NFTMarketplaceStateKeeper::get(self)
+            .listings
+            .get(&token_id)
+            .expect("NonFungibleToken: Token is not listed")
+    }
+
+    /// Releases the escrowed token to the buyer and pays out the price held by the program
+    fn complete_sale(
+        &mut self,
+        token_id: TokenId,
+        seller: &ActorId,
+        buyer: &ActorId,
+        currency: Currency,
+        price: u128,
+    ) -> NFTSale {
+        let payouts = self.nft_payout(token_id, seller, price);
+        for (account, amount) in payouts.iter() {
+            self.pay(currency, account, *amount);
+        }
+        self.release_token(buyer, token_id);
+        NFTStateKeeper::get_mut(self).record_price(token_id, price);
+        NFTSale {
+            token_id,
+            seller: *seller,
+            buyer: *buyer,
+            currency,
+            price,
+            payouts,
+        }
+    }
+
+    /// Moves the token held by the program to `to`
+    fn release_token(&mut self, to: &ActorId, token_id: TokenId) {
+        let program_id = exec::program_id();
+        self.move_token(&program_id, to, token_id);
+        // nested tokens follow their root
+        for descendant in NFTStateKeeper::get(self).descendants_of(token_id) {
+            self.move_token(&program_id, to, descendant);
+        }
+    }
+
+    /// Takes exactly `amount` from `account` into escrow of the program
+    fn take_payment(&mut self, currency: Currency, account: &ActorId, amount: u128) {
+        match currency {
+            Currency::Native => {
+                if msg::value() != amount {
+                    panic!("NonFungibleToken: Attached value must be equal to the price");
+                }
+            }
+            Currency::Token => {
+                if msg::value() != 0 {
+                    panic!("NonFungibleToken: Value can't be attached to token payments");
+                }
+                self.transfer_token(account, &exec::program_id(), amount);
+            }
+        }
+    }
+
+    /// Pays `amount` held by the program to `account`
+    fn pay(&mut self, currency: Currency, account: &ActorId, amount: u128) {
+        if amount == 0 {
+            return;
+        }
+        match currency {
+            Currency::Native => {
+                msg::send_bytes(*account, b"", amount).expect("Error during sending value");
+            }
+            Currency::Token => self.transfer_token(&exec::program_id(), account, amount),
+        }
+    }
+}
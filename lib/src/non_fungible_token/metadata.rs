@@ -0,0 +1,221 @@
+use crate::non_fungible_token::{io::*, nft_core::*, token::*};
+use blake2::{digest::consts::U32, Blake2b, Digest};
+use gstd::{msg, prelude::*};
+
+/// Returns the commitment to the metadata revealed later with `NFTMetadataCore::reveal`:
+/// the blake2b-256 hash of the SCALE-encoded metadata followed by `salt`
+pub fn metadata_commitment(metadata: &TokenMetadata, salt: &[u8]) -> [u8; 32] {
+    let mut hasher = Blake2b::<U32>::new();
+    hasher.update(metadata.encode());
+    hasher.update(salt);
+    hasher.finalize().into()
+}
+
+/// Updates of token metadata by the accounts from `NFTState::metadata_authorities`
+pub trait NFTMetadataCore: NFTCore {
+    /// Sets an attribute of the token, replacing the attribute with the same trait type
+    ///
+    /// Requirements:
+    /// * Only a metadata authority can call that action
+    /// * `token_id` must be the ID of the existing NFT
+    /// * The token metadata must not be frozen
+    ///
+    /// Arguments:
+    /// * `token_id`: the ID of the NFT
+    /// * `attribute`: the attribute to be set
+    fn set_attribute(
+        &mut self,
+        token_id: TokenId,
+        attribute: TokenAttribute,
+    ) -> NFTAttributeUpdate {
+        self.assert_can_update_metadata(token_id);
+        let attributes = &mut self
+            .get_mut()
+            .token_metadata_by_id
+            .entry(token_id)
+            .or_default()
+            .get_or_insert_with(Default::default)
+            .attributes;
+        if let Some(existing) = attributes
+            .iter_mut()
+            .find(|existing| existing.trait_type == attribute.trait_type)
+        {
+            *existing = attribute.clone();
+        } else {
+            attributes.push(attribute.clone());
+        }
+        NFTAttributeUpdate {
+            token_id,
+            trait_type: attribute.trait_type.clone(),
+            attribute: Some(attribute),
+        }
+    }
+
+    /// Removes an attribute of the token
+    ///
+    /// Requirements:
+    /// * Only a metadata authority can call that action
+    /// * `token_id` must be the ID of the existing NFT
+    /// * The token metadata must not be frozen
+    ///
+    /// Arguments:
+    /// * `token_id`: the ID of the NFT
+    /// * `trait_type`: the trait type of the attribute to be removed
+    fn remove_attribute(&mut self, token_id: TokenId, trait_type: String) -> NFTAttributeUpdate {
+        self.assert_can_update_metadata(token_id);
+        if let Some(Some(metadata)) = self.get_mut().token_metadata_by_id.get_mut(&token_id) {
+            metadata
+                .attributes
+                .retain(|attribute| attribute.trait_type != trait_type);
+        }
+        NFTAttributeUpdate {
+            token_id,
+            trait_type,
+            attribute: None,
+        }
+    }
+
+    /// Sets the base URI of token URIs
+    ///
+    /// Requirements:
+    /// * Only a metadata authority can call that action
+    /// * The metadata of the collection must not be frozen
+    ///
+    /// Arguments:
+    /// * `base_uri`: the new base URI, `{id}` in it is replaced by the token ID
+    fn set_base_uri(&mut self, base_uri: String) -> NFTMetadataUpdate {
+        self.assert_metadata_authority();
+        if self.get().metadata_frozen {
+            panic!("NonFungibleToken: Metadata is frozen");
+        }
+        self.get_mut().base_uri = base_uri;
+        NFTMetadataUpdate { token_id: None }
+    }
+
+    /// Replaces the whole metadata of the token
+    ///
+    /// Requirements:
+    /// * Only a metadata authority can call that action
+    /// * `token_id` must be the ID of the existing NFT
+    /// * The token metadata must not be frozen
+    ///
+    /// Arguments:
+    /// * `token_id`: the ID of the NFT
+    /// * `metadata`: the new metadata of the NFT
+    fn update_metadata(&mut self, token_id: TokenId, metadata: TokenMetadata) -> NFTMetadataUpdate {
+        self.assert_can_update_metadata(token_id);
+        self.get_mut()
+            .token_metadata_by_id
+            .insert(token_id, Some(metadata));
+        NFTMetadataUpdate {
+            token_id: Some(token_id),
+        }
+    }
+
+    /// Permanently freezes the metadata of the token
+    ///
+    /// A pending commitment of the token can still be revealed.
+    ///
+    /// Requirements:
+    /// * Only a metadata authority can call that action
+    /// * `token_id` must be the ID of the existing NFT
+    ///
+    /// Arguments:
+    /// * `token_id`: the ID of the NFT
+    fn freeze_metadata(&mut self, token_id: TokenId) -> NFTMetadataFreeze {
+        self.assert_metadata_authority();
+        // the token must exist
+        self.owner_of(token_id);
+        self.get_mut().frozen_metadata.insert(token_id);
+        NFTMetadataFreeze {
+            token_id: Some(token_id),
+        }
+    }
+
+    /// Permanently freezes the metadata of all tokens and the base URI
+    ///
+    /// Requirements:
+    /// * Only a metadata authority can call that action
+    fn freeze_all(&mut self) -> NFTMetadataFreeze {
+        self.assert_metadata_authority();
+        self.get_mut().metadata_frozen = true;
+        NFTMetadataFreeze { token_id: None }
+    }
+
+    /// Commits to the metadata that will be revealed later
+    ///
+    /// Requirements:
+    /// * Only a metadata authority can call that action
+    /// * `token_id` must be the ID of the existing NFT
+    /// * The token metadata must not be frozen
+    /// * The token must not have a pending commitment
+    ///
+    /// Arguments:
+    /// * `token_id`: the ID of the NFT
+    /// * `commitment`: the hash computed with `metadata_commitment`
+    fn commit_metadata(&mut self, token_id: TokenId, commitment: [u8; 32]) -> NFTMetadataCommit {
+        self.assert_can_update_metadata(token_id);
+        if self.get().metadata_commitments.contains_key(&token_id) {
+            panic!("NonFungibleToken: Metadata is already committed");
+        }
+        self.get_mut()
+            .metadata_commitments
+            .insert(token_id, commitment);
+        NFTMetadataCommit {
+            token_id,
+            commitment,
+        }
+    }
+
+    /// Reveals the committed metadata of the token
+    ///
+    /// Anyone knowing the metadata and the salt can call that action.
+    ///
+    /// Requirements:
+    /// * The token must have a pending commitment
+    /// * `metadata` and `salt` must match the commitment
+    ///
+    /// Arguments:
+    /// * `token_id`: the ID of the NFT
+    /// * `metadata`: the committed metadata
+    /// * `salt`: the salt used for the commitment
+    fn reveal(
+        &mut self,
+        token_id: TokenId,
+        metadata: TokenMetadata,
+        salt: Vec<u8>,
+    ) -> NFTMetadataUpdate {
+        let commitment = *self
+            .get()
+            .metadata_commitments
+            .get(&token_id)
+            .expect("NonFungibleToken: Token has no metadata commitment");
+        if metadata_commitment(&metadata, &salt) != commitment {
+            panic!("NonFungibleToken: Metadata does not match the commitment");
+        }
+        self.get_mut().metadata_commitments.remove(&token_id);
+        self.get_mut()
+            .token_metadata_by_id
+            .insert(token_id, Some(metadata));
+        NFTMetadataUpdate {
+            token_id: Some(token_id),
+        }
+    }
+
+    /// Checks that `msg::source()` can update the metadata of the existing token
+    fn assert_can_update_metadata(&self, token_id: TokenId) {
+        self.assert_metadata_authority();
+        // the token must exist
+        self.owner_of(token_id);
+        if self.get().is_metadata_frozen(token_id) {
+            panic!("NonFungibleToken: Metadata is frozen");
+        }
+    }
+
+    /// Checks that `msg::source()` is allowed to update token metadata
+    fn assert_metadata_authority(&self) {
+        if !self.get().metadata_authorities.contains(&msg::source()) {
+            panic!("NonFungibleToken: Not allowed to update metadata");
+        }
+    }
+}
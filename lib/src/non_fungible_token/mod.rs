@@ -1,6 +1,12 @@
+pub mod composable;
 pub mod delegated;
 pub mod io;
+pub mod lockable;
+pub mod marketplace;
+pub mod metadata;
 pub mod nft_core;
+pub mod rentable;
 pub mod royalties;
 pub mod state;
 pub mod token;
+pub mod voucher;
@@ -1,6 +1,7 @@
-use crate::non_fungible_token::{delegated::*, io::*, royalties::*, state::*, token::*};
-use gstd::{msg, prelude::*, ActorId};
-use hashbrown::HashSet;
+use crate::non_fungible_token::{
+    delegated::*, io::*, royalties::*, state::*, token::*, voucher::*,
+};
+use gstd::{exec, msg, prelude::*, ActorId};
 
 const ZERO_ID: ActorId = ActorId::zero();
 
@@ -10,6 +11,7 @@ pub trait NFTCore: NFTStateKeeper {
     /// Requirements:
     /// * `token_id` must be unique
     /// * `to` must be a non-zero account
+    /// * The number of tokens ever minted must be less than `max_supply`
     ///
     /// Arguments:
     /// * `to`: An account to which minted NFT will be assigned
@@ -23,15 +25,27 @@ pub trait NFTCore: NFTStateKeeper {
     ) -> NFTTransfer {
         self.assert_token_exists(token_id);
         self.assert_zero_address(to);
+        if let Some(max_supply) = self.get().max_supply {
+            if self.get().minted >= max_supply {
+                panic!("NonFungibleToken: Max supply is reached");
+            }
+        }
+        self.get_mut().minted += 1;
         self.get_mut().owner_by_id.insert(token_id, *to);
         self.get_mut()
             .tokens_for_owner
             .entry(*to)
-            .and_modify(|tokens| tokens.push(token_id))
-            .or_insert_with(|| vec![token_id]);
+            .or_default()
+            .insert(token_id);
         self.get_mut()
             .token_metadata_by_id
             .insert(token_id, token_metadata);
+        self.get_mut().record_transfer(token_id, &ZERO_ID, to);
+        if self.get().soulbound {
+            self.get_mut()
+                .soulbound_issuers
+                .insert(token_id, msg::source());
+        }
         NFTTransfer {
             from: ZERO_ID,
             to: *to,
@@ -39,11 +53,84 @@ pub trait NFTCore: NFTStateKeeper {
         }
     }
 
+    /// Mints a new token with the next free ID from the counter
+    ///
+    /// IDs taken by tokens minted with `mint` are skipped.
+    ///
+    /// Requirements:
+    /// * `to` must be a non-zero account
+    /// * The number of tokens ever minted must be less than `max_supply`
+    ///
+    /// Arguments:
+    /// * `to`: An account to which minted NFT will be assigned
+    /// * `token_metadata`: optional additional metadata about NFT
+    fn mint_next(&mut self, to: &ActorId, token_metadata: Option<TokenMetadata>) -> NFTTransfer {
+        let token_id = self.get().next_token_id();
+        let transfer = self.mint(to, token_id, token_metadata);
+        self.get_mut().next_token_id = token_id + TokenId::one();
+        transfer
+    }
+
+    /// Mints a new soulbound (non-transferable) token
+    ///
+    /// `msg::source()` becomes the issuer of the token and is able to burn it.
+    ///
+    /// Requirements:
+    /// * `token_id` must be unique
+    /// * `to` must be a non-zero account
+    ///
+    /// Arguments:
+    /// * `to`: An account to which minted NFT will be assigned
+    /// * `token_id`: the ID of minted NFT
+    /// * `token_metadata`: optional additional metadata about NFT
+    fn mint_soulbound(
+        &mut self,
+        to: &ActorId,
+        token_id: TokenId,
+        token_metadata: Option<TokenMetadata>,
+    ) -> NFTTransfer {
+        let transfer = self.mint(to, token_id, token_metadata);
+        self.get_mut()
+            .soulbound_issuers
+            .insert(token_id, msg::source());
+        transfer
+    }
+
+    /// Mints the token described by the voucher to `msg::source()`
+    /// and sends the attached value to the voucher creator
+    ///
+    /// Requirements:
+    /// * The voucher must be signed by its creator, who must be one of `NFTState::voucher_minters`
+    /// * The voucher must not be expired or already redeemed
+    /// * `msg::source()` must be the voucher recipient if it's set
+    /// * `msg::value()` must be at least the voucher price
+    ///
+    /// Arguments:
+    /// * `voucher`: the voucher of the NFT
+    /// * `signature`: the sr25519 signature of the encoded voucher
+    fn redeem_voucher(&mut self, voucher: MintVoucher, signature: [u8; 64]) -> NFTTransfer {
+        if !self.get().voucher_minters.contains(&voucher.creator) {
+            panic!("NonFungibleToken: Voucher creator is not a minter");
+        }
+        if self.get().redeemed_vouchers.contains(&voucher.token_id) {
+            panic!("NonFungibleToken: Voucher is already redeemed");
+        }
+        voucher.validate(&signature);
+
+        let transfer = self.mint(&msg::source(), voucher.token_id, voucher.metadata);
+        self.get_mut().redeemed_vouchers.insert(voucher.token_id);
+        let value = msg::value();
+        if value != 0 {
+            msg::send_bytes(voucher.creator, b"", value).expect("Error during sending value");
+        }
+        transfer
+    }
+
     /// Burns a token
     ///
     /// Requirements:
-    /// * Only NFT owner can call that action
-    /// * `token_id` must be the ID of the existing NFT
+    /// * Only NFT owner or the issuer of the soulbound NFT can call that action
+    /// * `token_id` must be the ID of the existing NFT which is neither attached nor has children
     ///
     /// Arguments:
     /// * `token_id`: the ID of  NFT that will be burnt
@@ -53,13 +140,27 @@ pub trait NFTCore: NFTStateKeeper {
             .owner_by_id
             .get(&token_id)
             .expect("NonFungibleToken: token does not exist");
-        self.assert_owner(&owner);
+        if self.get().soulbound_issuers.get(&token_id) != Some(&msg::source()) {
+            self.assert_owner(&owner);
+        }
+        self.assert_not_attached(token_id);
+        self.assert_not_locked(token_id);
+        if self.get().children_of.contains_key(&token_id)
+            || self.get().child_balances.contains_key(&token_id)
+        {
+            panic!("NonFungibleToken: Token has children");
+        }
         self.get_mut().owner_by_id.remove(&token_id);
+        self.get_mut().soulbound_issuers.remove(&token_id);
         self.get_mut().token_metadata_by_id.remove(&token_id);
-        self.get_mut()
-            .tokens_for_owner
-            .entry(owner)
-            .and_modify(|tokens| tokens.retain(|&token| token != token_id));
+        self.get_mut().clear_approvals(token_id);
+        self.get_mut().users.remove(&token_id);
+        self.get_mut().frozen_metadata.remove(&token_id);
+        self.get_mut().metadata_commitments.remove(&token_id);
+        self.get_mut().token_royalties.remove(&token_id);
+        self.get_mut().locks.remove(&token_id);
+        self.remove_from_owner(&owner, token_id);
+        self.get_mut().record_transfer(token_id, &owner, &ZERO_ID);
         NFTTransfer {
             from: owner,
             to: ZERO_ID,
@@ -67,12 +168,95 @@ pub trait NFTCore: NFTStateKeeper {
         }
     }
 
+    /// Mints several new tokens to one account
+    ///
+    /// Requirements:
+    /// * `tokens` must not be empty
+    /// * Token IDs must be unique
+    /// * `to` must be a non-zero account
+    /// * The number of tokens ever minted must not exceed `max_supply` after minting
+    ///
+    /// Arguments:
+    /// * `to`: An account to which minted NFTs will be assigned
+    /// * `tokens`: the IDs of minted NFTs with optional additional metadata
+    fn batch_mint(
+        &mut self,
+        to: &ActorId,
+        tokens: Vec<(TokenId, Option<TokenMetadata>)>,
+    ) -> NFTConsecutiveTransfer {
+        assert_not_empty(tokens.len());
+        let mut token_ids = Vec::with_capacity(tokens.len());
+        for (token_id, token_metadata) in tokens {
+            self.mint(to, token_id, token_metadata);
+            token_ids.push(token_id);
+        }
+        NFTConsecutiveTransfer {
+            from: ZERO_ID,
+            to: *to,
+            ranges: token_ranges(token_ids),
+        }
+    }
+
+    /// Transfers several tokens of one owner to the new owner
+    ///
+    /// Requirements:
+    /// * `token_ids` must not be empty and must belong to one owner
+    /// * Only the token owner or approved account can call that action
+    /// * `to` must be a non-zero account
+    /// * `token_ids` must be the IDs of existing non-soulbound unlocked NFTs
+    ///   not attached to a parent
+    ///
+    /// Arguments:
+    /// * `to`: An account to which NFTs will be transferred
+    /// * `token_ids`: the IDs of transferred NFTs
+    fn batch_transfer(&mut self, to: &ActorId, token_ids: Vec<TokenId>) -> NFTConsecutiveTransfer {
+        assert_not_empty(token_ids.len());
+        let from = self.owner_of(token_ids[0]);
+        for token_id in token_ids.iter() {
+            if self.owner_of(*token_id) != from {
+                panic!("NonFungibleToken: Tokens have different owners");
+            }
+            self.internal_transfer(to, *token_id);
+        }
+        NFTConsecutiveTransfer {
+            from,
+            to: *to,
+            ranges: token_ranges(token_ids),
+        }
+    }
+
+    /// Burns several tokens of one owner
+    ///
+    /// Requirements:
+    /// * `token_ids` must not be empty and must belong to one owner
+    /// * Only NFT owner or the issuer of the soulbound NFTs can call that action
+    /// * `token_ids` must be the IDs of existing NFTs which are neither attached nor have children
+    ///
+    /// Arguments:
+    /// * `token_ids`: the IDs of NFTs that will be burnt
+    fn batch_burn(&mut self, token_ids: Vec<TokenId>) -> NFTConsecutiveTransfer {
+        assert_not_empty(token_ids.len());
+        let from = self.owner_of(token_ids[0]);
+        for token_id in token_ids.iter() {
+            if self.owner_of(*token_id) != from {
+                panic!("NonFungibleToken: Tokens have different owners");
+            }
+            self.burn(*token_id);
+        }
+        NFTConsecutiveTransfer {
+            from,
+            to: ZERO_ID,
+            ranges: token_ranges(token_ids),
+        }
+    }
+
     /// Transfers a token to the new owner
     ///
     /// Requirements:
     /// * Only the token owner or approved account can call that action
     /// * `to` must be a non-zero account
-    /// * `token_id` must be the ID of the existing NFT
+    /// * `token_id` must be the ID of the existing non-soulbound unlocked NFT
+    ///   not attached to a parent
     ///
     /// Arguments:
     /// * `to`: An account to which NFT will be transferred
@@ -91,7 +275,8 @@ pub trait NFTCore: NFTStateKeeper {
     /// Requirements:
     /// * Only the token owner or approved account can call that action
     /// * `to` must be a non-zero account
-    /// * `token_id` must be the ID of the existing NFT
+    /// * `token_id` must be the ID of the existing non-soulbound unlocked NFT
+    ///   not attached to a parent
     ///
     /// Arguments:
     /// * `to`: An account to which NFT will be transferred
@@ -103,7 +288,8 @@ pub trait NFTCore: NFTStateKeeper {
         amount: u128,
     ) -> NFTTransferPayout {
         let owner = self.internal_transfer(to, token_id);
-        let payouts = self.nft_payout(&owner, amount);
+        self.get_mut().record_price(token_id, amount);
+        let payouts = self.nft_payout(token_id, &owner, amount);
         NFTTransferPayout {
             from: owner,
             to: *to,
@@ -118,8 +304,21 @@ pub trait NFTCore: NFTStateKeeper {
             .owner_by_id
             .get(&token_id)
             .expect("NonFungibleToken: token does not exist");
+        self.assert_transferable(token_id);
+        self.assert_not_attached(token_id);
+        self.assert_not_locked(token_id);
         self.assert_can_transfer(token_id, &owner);
         self.assert_zero_address(to);
+        self.move_token(&owner, to, token_id);
+        // nested tokens follow their root
+        for descendant in self.get().descendants_of(token_id) {
+            self.move_token(&owner, to, descendant);
+        }
+        owner
+    }
+
+    /// Reassigns a token from `from` to `to` without any checks
+    fn move_token(&mut self, from: &ActorId, to: &ActorId, token_id: TokenId) {
         // assign new owner
         self.get_mut()
             .owner_by_id
@@ -129,16 +328,26 @@ pub trait NFTCore: NFTStateKeeper {
         self.get_mut()
             .tokens_for_owner
             .entry(*to)
-            .and_modify(|tokens| tokens.push(token_id))
-            .or_insert_with(|| vec![token_id]);
+            .or_default()
+            .insert(token_id);
         // remove token from old owner
-        self.get_mut()
-            .tokens_for_owner
-            .entry(owner)
-            .and_modify(|tokens| tokens.retain(|&token| token != token_id));
+        self.remove_from_owner(from, token_id);
+        self.get_mut().record_transfer(token_id, from, to);
         // remove approvals if any
-        self.get_mut().token_approvals.remove(&token_id);
-        owner
+        self.get_mut().clear_approvals(token_id);
+        // remove the user of the rented token and the expired lock if any
+        self.get_mut().users.remove(&token_id);
+        self.get_mut().locks.remove(&token_id);
+    }
+
+    /// Removes a token from the tokens of `owner`
+    fn remove_from_owner(&mut self, owner: &ActorId, token_id: TokenId) {
+        if let Some(tokens) = self.get_mut().tokens_for_owner.get_mut(owner) {
+            tokens.remove(&token_id);
+            if tokens.is_empty() {
+                self.get_mut().tokens_for_owner.remove(owner);
+            }
+        }
     }
 
     /// Gives a right to another account to manage the token with indicated ID
@@ -146,26 +355,26 @@ pub trait NFTCore: NFTStateKeeper {
     /// Requirements:
     /// * Only the token owner can call that action
     /// * `to` must be a non-zero account
-    /// * `token_id` must be the ID of the existing NFT
+    /// * `token_id` must be the ID of the existing non-soulbound NFT
+    /// * `expires_at` must be in the future if present
     ///
     /// Arguments:
     /// * `to`: An account that will be approved to manage the indicated NFT
     /// * `token_id`: the ID of the NFT
-    fn approve(&mut self, to: &ActorId, token_id: TokenId) -> NFTApproval {
+    /// * `expires_at`: optional timestamp after which the approval is treated as absent
+    fn approve(&mut self, to: &ActorId, token_id: TokenId, expires_at: Option<u64>) -> NFTApproval {
         let owner = *self
             .get()
             .owner_by_id
             .get(&token_id)
             .expect("NonFungibleToken: token does not exist");
+        self.assert_transferable(token_id);
+        self.assert_not_locked(token_id);
         self.assert_owner(&owner);
         self.assert_zero_address(to);
-        self.get_mut()
-            .token_approvals
-            .entry(token_id)
-            .and_modify(|approvals| {
-                approvals.insert(*to);
-            })
-            .or_insert_with(|| HashSet::from([*to]));
+        self.get_mut().prune_expired_approvals(token_id);
+        self.get_mut().add_approval(to, token_id);
+        self.set_approval_expiration(to, token_id, expires_at);
         NFTApproval {
             owner,
             approved_account: *to,
@@ -180,12 +389,9 @@ pub trait NFTCore: NFTStateKeeper {
             .get(&token_id)
             .expect("NonFungibleToken: token does not exist");
         self.assert_owner(&owner);
-        self.get_mut()
-            .token_approvals
-            .entry(token_id)
-            .and_modify(|approvals| {
-                approvals.remove(approved_account);
-            });
+        self.get_mut().remove_approval(approved_account, token_id);
+        self.set_approval_expiration(approved_account, token_id, None);
+        self.get_mut().prune_expired_approvals(token_id);
         NFTApproval {
             owner,
             approved_account: ZERO_ID,
@@ -193,6 +399,33 @@ pub trait NFTCore: NFTStateKeeper {
         }
     }
 
+    /// Gives or removes a right to another account to manage all the tokens of `msg::source()`
+    ///
+    /// Requirements:
+    /// * `operator` must be a non-zero account other than `msg::source()`
+    ///
+    /// Arguments:
+    /// * `operator`: An account that will be able to manage all the tokens of the caller
+    /// * `approved`: Whether the right is given or removed
+    fn set_approval_for_all(&mut self, operator: &ActorId, approved: bool) -> NFTApprovalForAll {
+        let owner = msg::source();
+        self.assert_zero_address(operator);
+        if operator == &owner {
+            panic!("NonFungibleToken: Approve to caller");
+        }
+        let operators = self.get_mut().operator_approvals.entry(owner).or_default();
+        if approved {
+            operators.insert(*operator);
+        } else {
+            operators.remove(operator);
+        }
+        NFTApprovalForAll {
+            owner,
+            operator: *operator,
+            approved,
+        }
+    }
+
     fn owner_of(&self, token_id: TokenId) -> ActorId {
         *self
             .get()
@@ -202,11 +435,9 @@ pub trait NFTCore: NFTStateKeeper {
     }
 
     fn is_approved_to(&self, to: &ActorId, token_id: TokenId) -> bool {
-        self.get()
-            .token_approvals
-            .get(&token_id)
-            .expect("NonFungibleToken: token does not exist")
-            .contains(to)
+        // the token must exist
+        self.owner_of(token_id);
+        self.get().is_approved(to, token_id)
     }
 
     fn delegated_approve(
@@ -222,15 +453,13 @@ pub trait NFTCore: NFTStateKeeper {
             .get(&token_id)
             .expect("NonFungibleToken: token does not exist");
 
+        self.assert_transferable(token_id);
+        self.assert_not_locked(token_id);
         message.validate(&signed_approve, &owner);
 
-        self.get_mut()
-            .token_approvals
-            .entry(token_id)
-            .and_modify(|approvals| {
-                approvals.insert(*to);
-            })
-            .or_insert_with(|| HashSet::from([*to]));
+        self.get_mut().prune_expired_approvals(token_id);
+        self.get_mut().add_approval(to, token_id);
+        self.set_approval_expiration(to, token_id, None);
         NFTApproval {
             owner,
             approved_account: *to,
@@ -238,10 +467,67 @@ pub trait NFTCore: NFTStateKeeper {
         }
     }
 
+    /// Sets or removes the expiration timestamp of the approval
+    fn set_approval_expiration(
+        &mut self,
+        account: &ActorId,
+        token_id: TokenId,
+        expires_at: Option<u64>,
+    ) {
+        if let Some(expires_at) = expires_at {
+            if expires_at <= exec::block_timestamp() {
+                panic!("NonFungibleToken: Approval expiration is in the past");
+            }
+            self.get_mut()
+                .approval_expirations
+                .entry(token_id)
+                .or_default()
+                .insert(*account, expires_at);
+        } else if let Some(expirations) = self.get_mut().approval_expirations.get_mut(&token_id) {
+            expirations.remove(account);
+        }
+    }
+
+    /// Sets or removes the royalties of the token or the collection
+    ///
+    /// The program must check that `msg::source()` is allowed to call that action.
+    ///
+    /// Requirements:
+    /// * `token_id` must be the ID of the existing NFT if it's set
+    /// * The percents of `royalties` must be valid
+    ///
+    /// Arguments:
+    /// * `token_id`: the ID of the NFT, `None` for the collection royalties
+    /// * `royalties`: the new royalties, `None` to remove them
+    fn set_royalties(
+        &mut self,
+        token_id: Option<TokenId>,
+        royalties: Option<Royalties>,
+    ) -> NFTRoyaltiesUpdate {
+        if let Some(royalties) = &royalties {
+            royalties.validate();
+        }
+        match (token_id, royalties.clone()) {
+            (Some(token_id), Some(royalties)) => {
+                // the token must exist
+                self.owner_of(token_id);
+                self.get_mut().token_royalties.insert(token_id, royalties);
+            }
+            (Some(token_id), None) => {
+                self.get_mut().token_royalties.remove(&token_id);
+            }
+            (None, royalties) => self.get_mut().royalties = royalties,
+        }
+        NFTRoyaltiesUpdate {
+            token_id,
+            royalties,
+        }
+    }
+
     /// Returns a `Payout` struct for a given token
-    /// If NFT contract has no royalties it just returns BtreeMap {“owner”: "amount"}
-    fn nft_payout(&self, owner: &ActorId, amount: u128) -> Payout {
-        if let Some(ref royalties) = self.get().royalties {
+    /// If the token and NFT contract have no royalties it just returns BtreeMap {“owner”: "amount"}
+    fn nft_payout(&self, token_id: TokenId, owner: &ActorId, amount: u128) -> Payout {
+        if let Some(royalties) = self.get().royalties_of(token_id) {
             royalties.payouts(owner, amount)
         } else {
             [(*owner, amount)].into()
@@ -262,10 +548,40 @@ pub trait NFTCore: NFTStateKeeper {
         }
     }
 
+    /// Checks that the token with indicated `token_id` isn't soulbound
+    fn assert_transferable(&self, token_id: TokenId) {
+        if self.get().is_soulbound(token_id) {
+            panic!("NonFungibleToken: Token is soulbound");
+        }
+    }
+
+    /// Checks that neither the token nor the tokens nested into it are locked
+    fn assert_not_locked(&self, token_id: TokenId) {
+        if self.get().is_locked(token_id)
+            || self
+                .get()
+                .descendants_of(token_id)
+                .into_iter()
+                .any(|descendant| self.get().is_locked(descendant))
+        {
+            panic!("NonFungibleToken: Token is locked");
+        }
+    }
+
+    /// Checks that the token with indicated `token_id` isn't nested into another token
+    fn assert_not_attached(&self, token_id: TokenId) {
+        if self.get().parent_of.contains_key(&token_id) {
+            panic!("NonFungibleToken: Token is attached to a parent");
+        }
+    }
+
     /// Checks that `msg::source()` is allowed to manage the token with indicated `token_id`
     fn assert_can_transfer(&self, token_id: TokenId, owner: &ActorId) {
-        if let Some(approved_accounts) = self.get().token_approvals.get(&token_id) {
-            if approved_accounts.contains(&msg::source()) {
+        if self.get().is_approved(&msg::source(), token_id) {
+            return;
+        }
+        if let Some(operators) = self.get().operator_approvals.get(owner) {
+            if operators.contains(&msg::source()) {
                 return;
             }
         }
@@ -279,3 +595,95 @@ pub trait NFTCore: NFTStateKeeper {
         }
     }
 }
+
+fn assert_not_empty(len: usize) {
+    if len == 0 {
+        panic!("NonFungibleToken: Empty batch");
+    }
+}
+
+/// Compresses token IDs into inclusive ranges of consecutive IDs
+fn token_ranges(mut token_ids: Vec<TokenId>) -> Vec<(TokenId, TokenId)> {
+    token_ids.sort_unstable();
+    token_ids.dedup();
+    let mut ranges: Vec<(TokenId, TokenId)> = Vec::new();
+    for token_id in token_ids {
+        match ranges.last_mut() {
+            Some((_, last)) if last.checked_add(TokenId::one()) == Some(token_id) => {
+                *last = token_id
+            }
+            _ => ranges.push((token_id, token_id)),
+        }
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod benches {
+    extern crate test;
+
+    use super::*;
+    use test::Bencher;
+
+    #[derive(Default)]
+    struct Collection(NFTState);
+
+    impl NFTStateKeeper for Collection {
+        fn get(&self) -> &NFTState {
+            &self.0
+        }
+
+        fn get_mut(&mut self) -> &mut NFTState {
+            &mut self.0
+        }
+    }
+
+    impl NFTCore for Collection {}
+
+    const OWNER: u64 = 1;
+    const RECIPIENT: u64 = 2;
+
+    /// Creates a collection where the owner holds `holdings` tokens with IDs `0..holdings`
+    fn collection(holdings: u64) -> Collection {
+        let mut collection = Collection::default();
+        let owner = ActorId::from(OWNER);
+        for id in 0..holdings {
+            let token_id = TokenId::from(id);
+            collection.0.owner_by_id.insert(token_id, owner);
+            collection
+                .0
+                .tokens_for_owner
+                .entry(owner)
+                .or_default()
+                .insert(token_id);
+        }
+        collection
+    }
+
+    /// Moves a token from the middle of the owner's holdings there and back
+    fn bench_transfer(b: &mut Bencher, holdings: u64) {
+        let mut collection = collection(holdings);
+        let (owner, recipient) = (ActorId::from(OWNER), ActorId::from(RECIPIENT));
+        let token_id = TokenId::from(holdings / 2);
+        b.iter(|| {
+            collection.move_token(&owner, &recipient, token_id);
+            collection.move_token(&recipient, &owner, token_id);
+        });
+        assert_eq!(collection.0.tokens_for_owner[&owner].len() as u64, holdings);
+    }
+
+    #[bench]
+    fn transfer_with_10_holdings(b: &mut Bencher) {
+        bench_transfer(b, 10);
+    }
+
+    #[bench]
+    fn transfer_with_1k_holdings(b: &mut Bencher) {
+        bench_transfer(b, 1_000);
+    }
+
+    #[bench]
+    fn transfer_with_100k_holdings(b: &mut Bencher) {
+        bench_transfer(b, 100_000);
+    }
+}
@@ -0,0 +1,44 @@
+use crate::non_fungible_token::{io::*, nft_core::*, token::*};
+use gstd::{prelude::*, ActorId};
+
+#[derive(
+    Default, Debug, Encode, Decode, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, TypeInfo, Hash,
+)]
+pub struct UserInfo {
+    pub user: ActorId,
+    pub expires: u64,
+}
+
+/// ERC-4907 user role: the owner keeps the token while the user can use it until `expires`
+pub trait NFTRentable: NFTCore {
+    /// Sets the user of the token, the user is removed when the token is transferred
+    ///
+    /// Requirements:
+    /// * Only the token owner or approved account can call that action
+    /// * `token_id` must be the ID of the existing NFT
+    ///
+    /// Arguments:
+    /// * `token_id`: the ID of the NFT
+    /// * `user`: An account that will be able to use the NFT, a zero address removes the user
+    /// * `expires`: The timestamp (in milliseconds) until which the user can use the NFT
+    fn set_user(&mut self, token_id: TokenId, user: &ActorId, expires: u64) -> NFTUpdateUser {
+        let owner = self.owner_of(token_id);
+        self.assert_can_transfer(token_id, &owner);
+        if user == &ActorId::zero() {
+            self.get_mut().users.remove(&token_id);
+        } else {
+            self.get_mut().users.insert(
+                token_id,
+                UserInfo {
+                    user: *user,
+                    expires,
+                },
+            );
+        }
+        NFTUpdateUser {
+            token_id,
+            user: *user,
+            expires,
+        }
+    }
+}
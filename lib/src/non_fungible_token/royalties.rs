@@ -1,4 +1,5 @@
 use gstd::{prelude::*, ActorId};
+use primitive_types::U256;
 
 pub type Payout = BTreeMap<ActorId, u128>;
 
@@ -9,34 +10,207 @@ pub struct Royalties {
 }
 
 impl Royalties {
+    /// Creates royalties of `percent` of the sale amount (in hundredths of a percent)
+    /// split between `accounts` by their percents (in hundredths of a percent)
+    pub fn new(accounts: Payout, percent: u16) -> Self {
+        let royalties = Self { accounts, percent };
+        royalties.validate();
+        royalties
+    }
+
+    pub fn accounts(&self) -> &Payout {
+        &self.accounts
+    }
+
+    pub fn percent(&self) -> u16 {
+        self.percent
+    }
+
     pub fn validate(&self) {
+        if let Err(error) = self.check() {
+            panic!("{}", error);
+        }
+    }
+
+    fn check(&self) -> Result<(), &'static str> {
         // percent must be less than or equal to 100% (100 * 100)
         if self.percent > 10_000u16 {
-            panic!("royalty percent must be less than 100%");
+            return Err("royalty percent must be less than 100%");
         }
-        let mut total_percents = 0;
-        self.accounts.iter().for_each(|(_, percent)| {
-            if *percent as u16 > 10_000u16 {
-                panic!("account percent must be less than or equal to 100%");
+        let mut total_percents: u128 = 0;
+        for percent in self.accounts.values() {
+            if *percent > 10_000 {
+                return Err("account percent must be less than or equal to 100%");
             }
+            // can't overflow since every percent is at most 10_000
             total_percents += percent;
-        });
-        if total_percents as u16 > 10_000u16 {
-            panic!("total percent of royalty be less than or equal to 100%");
         }
+        if total_percents > 10_000 {
+            return Err("total percent of royalty be less than or equal to 100%");
+        }
+        Ok(())
     }
 
-    pub fn payouts(&self, owner: &ActorId, amount: u128) -> Payout {
-        let royalty_payment = amount * self.percent as u128 / 10_000;
-        let mut payouts: Payout = self
-            .accounts
+    /// Returns the part of the sale `amount` paid as royalties
+    pub fn royalty_amount(&self, amount: u128) -> u128 {
+        share(amount, self.percent.into())
+    }
+
+    /// Returns the royalties of the accounts from the sale `amount`
+    pub fn royalty_payouts(&self, amount: u128) -> Payout {
+        let royalty_payment = self.royalty_amount(amount);
+        self.accounts
             .iter()
-            .map(|(account, percent)| (*account, *percent * royalty_payment / 10_000))
-            .collect();
+            .map(|(account, percent)| (*account, share(royalty_payment, *percent)))
+            .collect()
+    }
+
+    /// Splits the sale `amount` between the royalty accounts and the owner
+    ///
+    /// The payouts always sum up to `amount`: the rounding remainder of the royalties
+    /// and the royalty percents not assigned to the accounts are paid to the owner.
+    /// If the owner is one of the accounts, the payouts are added up.
+    pub fn payouts(&self, owner: &ActorId, amount: u128) -> Payout {
+        self.validate();
+        let mut payouts = self.royalty_payouts(amount);
+        let paid: u128 = payouts.values().sum();
 
-        let rest = amount - royalty_payment;
-        let owner_payout = payouts.get(owner).map_or(0, |p| *p) + rest;
-        payouts.insert(*owner, owner_payout);
+        let rest = amount - paid;
+        *payouts.entry(*owner).or_default() += rest;
         payouts
     }
 }
+
+/// Returns `percent` (in hundredths of a percent) of `amount` rounded down
+fn share(amount: u128, percent: u128) -> u128 {
+    let share = U256::from(amount) * U256::from(percent) / U256::from(10_000);
+    // `percent` is at most 100% after validation, so the share fits into `amount`
+    share.try_into().expect("royalty share overflow")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CASES: usize = 10_000;
+
+    /// xorshift64* generator, so the cases are reproducible without extra dependencies
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 >> 12;
+            self.0 ^= self.0 << 25;
+            self.0 ^= self.0 >> 27;
+            self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+        }
+
+        fn below(&mut self, bound: u64) -> u64 {
+            self.next() % bound
+        }
+
+        fn u128(&mut self) -> u128 {
+            (self.next() as u128) << 64 | self.next() as u128
+        }
+
+        /// Returns a percent that is small, around 100% or anywhere in the `u128` domain
+        fn percent(&mut self) -> u128 {
+            match self.below(4) {
+                0 => self.below(3_000) as u128,
+                1 => 9_990 + self.below(20) as u128,
+                2 => u16::MAX as u128 + self.below(20_000) as u128,
+                _ => self.u128(),
+            }
+        }
+
+        /// Returns an amount that is small or anywhere in the `u128` domain
+        fn amount(&mut self) -> u128 {
+            match self.below(3) {
+                0 => self.below(10_000) as u128,
+                1 => u128::MAX - self.below(10_000) as u128,
+                _ => self.u128(),
+            }
+        }
+
+        fn royalties(&mut self, max_percent: Option<u128>) -> Royalties {
+            let accounts = (0..self.below(6))
+                .map(|_| {
+                    let percent = match max_percent {
+                        Some(max_percent) => self.below(max_percent as u64 + 1) as u128,
+                        None => self.percent(),
+                    };
+                    (ActorId::from(self.below(8)), percent)
+                })
+                .collect();
+            let percent = match self.below(3) {
+                0 => self.next() as u16,
+                _ => self.below(10_001) as u16,
+            };
+            Royalties { accounts, percent }
+        }
+    }
+
+    fn is_valid(royalties: &Royalties) -> bool {
+        let total = royalties
+            .accounts
+            .values()
+            .try_fold(0u128, |total, percent| total.checked_add(*percent));
+        royalties.percent <= 10_000 && matches!(total, Some(total) if total <= 10_000)
+    }
+
+    #[test]
+    fn validation_is_exact() {
+        let mut rng = Rng(0x5eed);
+        for _ in 0..CASES {
+            let royalties = rng.royalties(None);
+            assert_eq!(
+                royalties.check().is_ok(),
+                is_valid(&royalties),
+                "{royalties:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn validation_rejects_percents_wrapping_u16() {
+        let royalties = Royalties {
+            accounts: [(ActorId::from(1), 65_536 + 5_000)].into(),
+            percent: 1_000,
+        };
+        assert!(royalties.check().is_err());
+    }
+
+    #[test]
+    fn payouts_sum_up_to_amount() {
+        let mut rng = Rng(0xfee);
+        for _ in 0..CASES {
+            let royalties = rng.royalties(Some(10_000 / 5));
+            if royalties.check().is_err() {
+                continue;
+            }
+            // the owner is one of the accounts in a part of the cases
+            let owner = ActorId::from(rng.below(8));
+            let amount = rng.amount();
+
+            let payouts = royalties.payouts(&owner, amount);
+
+            let total = payouts
+                .values()
+                .try_fold(0u128, |total, payout| total.checked_add(*payout));
+            assert_eq!(total, Some(amount), "{royalties:?} {amount}");
+            assert!(payouts[&owner] >= amount - royalties.royalty_amount(amount));
+        }
+    }
+
+    #[test]
+    fn owner_in_accounts_gets_both_payouts() {
+        let owner = ActorId::from(1);
+        let royalties = Royalties::new([(owner, 5_000), (ActorId::from(2), 5_000)].into(), 1_000);
+
+        let payouts = royalties.payouts(&owner, 1_001);
+
+        // 100 of royalties split in halves, the rounding remainder goes to the owner
+        assert_eq!(payouts[&ActorId::from(2)], 50);
+        assert_eq!(payouts[&owner], 951);
+    }
+}
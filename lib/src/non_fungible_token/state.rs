@@ -1,5 +1,5 @@
-use crate::non_fungible_token::{royalties::*, token::*};
-use gstd::{prelude::*, ActorId};
+use crate::non_fungible_token::{composable::*, lockable::*, rentable::*, royalties::*, token::*};
+use gstd::{exec, prelude::*, ActorId};
 use hashbrown::{HashMap, HashSet};
 
 #[derive(Default, Debug, PartialEq, Eq, Clone)]
@@ -9,9 +9,242 @@ pub struct NFTState {
     pub base_uri: String,
     pub owner_by_id: HashMap<TokenId, ActorId>,
     pub token_approvals: HashMap<TokenId, HashSet<ActorId>>,
+    /// Tokens approved to accounts, the reverse index of `token_approvals`.
+    pub approvals_by_account: HashMap<ActorId, BTreeSet<TokenId>>,
+    /// Timestamps after which token approvals are treated as absent.
+    pub approval_expirations: HashMap<TokenId, HashMap<ActorId, u64>>,
+    pub operator_approvals: HashMap<ActorId, HashSet<ActorId>>,
     pub token_metadata_by_id: HashMap<TokenId, Option<TokenMetadata>>,
-    pub tokens_for_owner: HashMap<ActorId, Vec<TokenId>>,
+    pub tokens_for_owner: HashMap<ActorId, BTreeSet<TokenId>>,
     pub royalties: Option<Royalties>,
+    /// Royalties of tokens overriding the collection `royalties`.
+    pub token_royalties: HashMap<TokenId, Royalties>,
+    /// Users of rented tokens.
+    pub users: HashMap<TokenId, UserInfo>,
+    /// Makes all the tokens soulbound (non-transferable).
+    pub soulbound: bool,
+    /// Issuers of soulbound tokens, who are able to burn them.
+    pub soulbound_issuers: HashMap<TokenId, ActorId>,
+    /// Parents of nested tokens.
+    pub parent_of: HashMap<TokenId, TokenId>,
+    /// Tokens nested into a token.
+    pub children_of: HashMap<TokenId, BTreeSet<TokenId>>,
+    /// Balances of other contracts owned by a token.
+    pub child_balances: HashMap<TokenId, ChildBalances>,
+    /// Accounts allowed to update token metadata.
+    pub metadata_authorities: HashSet<ActorId>,
+    /// Makes the metadata of all tokens and `base_uri` permanent.
+    pub metadata_frozen: bool,
+    /// Tokens with permanent metadata.
+    pub frozen_metadata: HashSet<TokenId>,
+    /// Committed hashes of the metadata of unrevealed tokens.
+    pub metadata_commitments: HashMap<TokenId, [u8; 32]>,
+    /// Accounts whose mint vouchers can be redeemed.
+    pub voucher_minters: HashSet<ActorId>,
+    /// Tokens minted by vouchers, which can't be redeemed again.
+    pub redeemed_vouchers: HashSet<TokenId>,
+    /// The ID tried first by `NFTCore::mint_next`.
+    pub next_token_id: TokenId,
+    /// The maximum number of tokens ever minted including burnt ones, unlimited if `None`.
+    pub max_supply: Option<u128>,
+    /// The number of tokens ever minted including burnt ones.
+    pub minted: u128,
+    /// The maximum number of the latest records kept in the history of each token,
+    /// the history isn't recorded if `None`.
+    pub history_limit: Option<u32>,
+    /// Provenance history of tokens from the oldest record.
+    pub history: HashMap<TokenId, Vec<TransferRecord>>,
+    /// Locks of tokens, expired locks are treated as absent.
+    pub locks: HashMap<TokenId, TokenLock>,
+}
+
+impl NFTState {
+    /// Checks that `account` has an unexpired approval for the token
+    pub fn is_approved(&self, account: &ActorId, token_id: TokenId) -> bool {
+        self.token_approvals
+            .get(&token_id)
+            .map(|approvals| approvals.contains(account))
+            .unwrap_or(false)
+            && !self.is_approval_expired(account, token_id)
+    }
+
+    pub fn is_approval_expired(&self, account: &ActorId, token_id: TokenId) -> bool {
+        self.approval_expirations
+            .get(&token_id)
+            .and_then(|expirations| expirations.get(account))
+            .map(|expires_at| *expires_at <= exec::block_timestamp())
+            .unwrap_or(false)
+    }
+
+    /// Checks whether the token is soulbound (non-transferable)
+    pub fn is_soulbound(&self, token_id: TokenId) -> bool {
+        (self.soulbound && self.owner_by_id.contains_key(&token_id))
+            || self.soulbound_issuers.contains_key(&token_id)
+    }
+
+    /// Checks whether the token has an unexpired lock
+    pub fn is_locked(&self, token_id: TokenId) -> bool {
+        self.locks
+            .get(&token_id)
+            .map(|lock| lock.until > exec::block_timestamp())
+            .unwrap_or(false)
+    }
+
+    /// Checks whether the metadata of the token is permanent
+    pub fn is_metadata_frozen(&self, token_id: TokenId) -> bool {
+        self.metadata_frozen || self.frozen_metadata.contains(&token_id)
+    }
+
+    /// Returns the first ID starting from `next_token_id` that isn't taken by an existing token
+    pub fn next_token_id(&self) -> TokenId {
+        let mut token_id = self.next_token_id;
+        while self.owner_by_id.contains_key(&token_id) {
+            token_id += TokenId::one();
+        }
+        token_id
+    }
+
+    /// Returns the royalties of the token or the collection royalties if it has none
+    pub fn royalties_of(&self, token_id: TokenId) -> Option<&Royalties> {
+        self.token_royalties
+            .get(&token_id)
+            .or(self.royalties.as_ref())
+    }
+
+    /// Returns the top-level token the token is nested into or the token itself
+    pub fn root_of(&self, token_id: TokenId) -> TokenId {
+        let mut root_id = token_id;
+        while let Some(parent_id) = self.parent_of.get(&root_id) {
+            root_id = *parent_id;
+        }
+        root_id
+    }
+
+    /// Returns all the tokens nested into the token at any depth
+    pub fn descendants_of(&self, token_id: TokenId) -> Vec<TokenId> {
+        let mut descendants = Vec::new();
+        let mut pending = vec![token_id];
+        while let Some(id) = pending.pop() {
+            if let Some(children) = self.children_of.get(&id) {
+                descendants.extend(children.iter().copied());
+                pending.extend(children.iter().copied());
+            }
+        }
+        descendants
+    }
+
+    /// Returns the current user of the token or a zero address if there is none
+    pub fn user_of(&self, token_id: TokenId) -> ActorId {
+        match self.users.get(&token_id) {
+            Some(info) if info.expires > exec::block_timestamp() => info.user,
+            _ => ActorId::zero(),
+        }
+    }
+
+    /// Returns the timestamp until which the current user can use the token
+    pub fn user_expires(&self, token_id: TokenId) -> u64 {
+        match self.users.get(&token_id) {
+            Some(info) if info.expires > exec::block_timestamp() => info.expires,
+            _ => 0,
+        }
+    }
+
+    /// Removes expired approvals of the token
+    pub fn prune_expired_approvals(&mut self, token_id: TokenId) {
+        let Some(expirations) = self.approval_expirations.get_mut(&token_id) else {
+            return;
+        };
+        let now = exec::block_timestamp();
+        let approvals = self.token_approvals.entry(token_id).or_default();
+        let approvals_by_account = &mut self.approvals_by_account;
+        expirations.retain(|account, expires_at| {
+            if *expires_at <= now {
+                approvals.remove(account);
+                remove_from_index(approvals_by_account, account, token_id);
+                false
+            } else {
+                true
+            }
+        });
+        if expirations.is_empty() {
+            self.approval_expirations.remove(&token_id);
+        }
+    }
+
+    /// Appends a record to the history of the token, dropping the oldest records over the limit
+    pub fn record_transfer(&mut self, token_id: TokenId, from: &ActorId, to: &ActorId) {
+        let Some(limit) = self.history_limit else {
+            return;
+        };
+        let records = self.history.entry(token_id).or_default();
+        records.push(TransferRecord {
+            from: *from,
+            to: *to,
+            block: exec::block_height(),
+            timestamp: exec::block_timestamp(),
+            price: None,
+        });
+        if records.len() > limit as usize {
+            records.drain(..records.len() - limit as usize);
+        }
+        if records.is_empty() {
+            self.history.remove(&token_id);
+        }
+    }
+
+    /// Sets the sale price of the last transfer of the token
+    pub fn record_price(&mut self, token_id: TokenId, price: u128) {
+        if let Some(record) = self
+            .history
+            .get_mut(&token_id)
+            .and_then(|records| records.last_mut())
+        {
+            record.price = Some(price);
+        }
+    }
+
+    /// Approves the token to `account`
+    pub fn add_approval(&mut self, account: &ActorId, token_id: TokenId) {
+        self.token_approvals
+            .entry(token_id)
+            .or_default()
+            .insert(*account);
+        self.approvals_by_account
+            .entry(*account)
+            .or_default()
+            .insert(token_id);
+    }
+
+    /// Removes the approval of the token to `account`
+    pub fn remove_approval(&mut self, account: &ActorId, token_id: TokenId) {
+        if let Some(approvals) = self.token_approvals.get_mut(&token_id) {
+            approvals.remove(account);
+        }
+        remove_from_index(&mut self.approvals_by_account, account, token_id);
+    }
+
+    /// Removes all the approvals of the token and their expirations
+    pub fn clear_approvals(&mut self, token_id: TokenId) {
+        if let Some(approvals) = self.token_approvals.remove(&token_id) {
+            for account in approvals.iter() {
+                remove_from_index(&mut self.approvals_by_account, account, token_id);
+            }
+        }
+        self.approval_expirations.remove(&token_id);
+    }
+}
+
+fn remove_from_index(
+    index: &mut HashMap<ActorId, BTreeSet<TokenId>>,
+    account: &ActorId,
+    token_id: TokenId,
+) {
+    if let Some(tokens) = index.get_mut(account) {
+        tokens.remove(&token_id);
+        if tokens.is_empty() {
+            index.remove(account);
+        }
+    }
 }
 
 pub trait NFTStateKeeper {
@@ -22,12 +255,67 @@ pub trait NFTStateKeeper {
 #[derive(Debug, Encode, Decode, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, TypeInfo, Hash)]
 pub enum NFTQuery {
     NFTInfo,
-    Token { token_id: TokenId },
-    TokensForOwner { owner: ActorId },
+    Token {
+        token_id: TokenId,
+    },
+    TokensForOwner {
+        owner: ActorId,
+    },
     TotalSupply,
-    SupplyForOwner { owner: ActorId },
+    SupplyForOwner {
+        owner: ActorId,
+    },
     AllTokens,
-    ApprovedTokens { account: ActorId },
+    ApprovedTokens {
+        account: ActorId,
+    },
+    IsApprovedForAll {
+        owner: ActorId,
+        operator: ActorId,
+    },
+    UserOf {
+        token_id: TokenId,
+    },
+    UserExpires {
+        token_id: TokenId,
+    },
+    Locked {
+        token_id: TokenId,
+    },
+    TokenTree {
+        token_id: TokenId,
+    },
+    TokenURI {
+        token_id: TokenId,
+    },
+    MaxSupply,
+    NextTokenId,
+    RoyaltyInfo {
+        token_id: TokenId,
+        sale_price: u128,
+    },
+    AllTokensPage {
+        offset: u32,
+        limit: u32,
+    },
+    TokensForOwnerPage {
+        owner: ActorId,
+        offset: u32,
+        limit: u32,
+    },
+    ApprovedTokensPage {
+        account: ActorId,
+        offset: u32,
+        limit: u32,
+    },
+    History {
+        token_id: TokenId,
+        offset: u32,
+        limit: u32,
+    },
+    LockedTokens {
+        owner: ActorId,
+    },
 }
 
 #[derive(Debug, Encode, Decode, PartialEq, Eq, PartialOrd, Ord, Clone, TypeInfo, Hash)]
@@ -55,6 +343,53 @@ pub enum NFTQueryReply {
     ApprovedTokens {
         tokens: Vec<Token>,
     },
+    IsApprovedForAll {
+        approved: bool,
+    },
+    UserOf {
+        user: ActorId,
+    },
+    UserExpires {
+        expires: u64,
+    },
+    Locked {
+        locked: bool,
+    },
+    TokenTree {
+        tree: TokenTree,
+    },
+    TokenURI {
+        uri: String,
+    },
+    MaxSupply {
+        max_supply: Option<u128>,
+    },
+    NextTokenId {
+        token_id: TokenId,
+    },
+    RoyaltyInfo {
+        royalty_amount: u128,
+        receivers: Payout,
+    },
+    AllTokensPage {
+        tokens: Vec<Token>,
+        total: u32,
+    },
+    TokensForOwnerPage {
+        tokens: Vec<Token>,
+        total: u32,
+    },
+    ApprovedTokensPage {
+        tokens: Vec<Token>,
+        total: u32,
+    },
+    History {
+        records: Vec<TransferRecord>,
+        total: u32,
+    },
+    LockedTokens {
+        tokens: Vec<TokenId>,
+    },
 }
 
 pub trait NFTMetaState: NFTStateKeeper {
@@ -65,17 +400,40 @@ pub trait NFTMetaState: NFTStateKeeper {
             token.owner_id = *owner_id;
         }
         if let Some(approved_account_ids) = self.get().token_approvals.get(&token_id) {
-            token.approved_account_ids = approved_account_ids.iter().copied().collect();
+            token.approved_account_ids = approved_account_ids
+                .iter()
+                .filter(|account| !self.get().is_approval_expired(account, token_id))
+                .copied()
+                .collect();
         }
         if let Some(Some(metadata)) = self.get().token_metadata_by_id.get(&token_id) {
             token.name = metadata.name.clone();
             token.description = metadata.description.clone();
             token.media = metadata.media.clone();
             token.reference = metadata.reference.clone();
+            token.attributes = metadata.attributes.clone();
         }
         token
     }
 
+    /// Returns the URI of the token: the `reference` from its metadata if set,
+    /// otherwise `base_uri` with `{id}` replaced by the token ID or the ID appended
+    fn token_uri(&self, token_id: TokenId) -> String {
+        if let Some(Some(metadata)) = self.get().token_metadata_by_id.get(&token_id) {
+            if !metadata.reference.is_empty() {
+                return metadata.reference.clone();
+            }
+        }
+        let base_uri = &self.get().base_uri;
+        if base_uri.is_empty() || !self.get().owner_by_id.contains_key(&token_id) {
+            String::new()
+        } else if base_uri.contains("{id}") {
+            base_uri.replace("{id}", &format!("{token_id}"))
+        } else {
+            format!("{base_uri}{token_id}")
+        }
+    }
+
     fn tokens_for_owner(&self, owner: &ActorId) -> Vec<Token> {
         let mut tokens: Vec<Token> = Vec::new();
         if let Some(token_ids) = self.get().tokens_for_owner.get(owner) {
@@ -106,18 +464,126 @@ pub trait NFTMetaState: NFTStateKeeper {
     }
     fn approved_tokens(&self, account: &ActorId) -> Vec<Token> {
         self.get()
-            .owner_by_id
-            .keys()
-            .filter_map(|id| {
-                self.get().token_approvals.get(id).and_then(|approvals| {
-                    if approvals.contains(account) {
-                        Some(self.token(*id))
-                    } else {
-                        None
-                    }
-                })
+            .approvals_by_account
+            .get(account)
+            .map(|token_ids| {
+                token_ids
+                    .iter()
+                    .filter(|id| self.get().is_approved(account, **id))
+                    .map(|id| self.token(*id))
+                    .collect()
             })
-            .collect()
+            .unwrap_or_default()
+    }
+
+    /// Returns `limit` tokens starting from `offset` ordered by ID and the total number of tokens
+    fn all_tokens_page(&self, offset: u32, limit: u32) -> (Vec<Token>, u32) {
+        let mut token_ids: Vec<TokenId> = self.get().owner_by_id.keys().copied().collect();
+        token_ids.sort_unstable();
+        self.tokens_page(token_ids, offset, limit)
+    }
+
+    /// Returns `limit` tokens of `owner` starting from `offset` ordered by ID
+    /// and the total number of tokens of `owner`
+    fn tokens_for_owner_page(&self, owner: &ActorId, offset: u32, limit: u32) -> (Vec<Token>, u32) {
+        let token_ids = self
+            .get()
+            .tokens_for_owner
+            .get(owner)
+            .map(|token_ids| token_ids.iter().copied().collect())
+            .unwrap_or_default();
+        self.tokens_page(token_ids, offset, limit)
+    }
+
+    /// Returns `limit` tokens approved to `account` starting from `offset` ordered by ID
+    /// and the total number of tokens approved to `account`
+    fn approved_tokens_page(
+        &self,
+        account: &ActorId,
+        offset: u32,
+        limit: u32,
+    ) -> (Vec<Token>, u32) {
+        let token_ids = self
+            .get()
+            .approvals_by_account
+            .get(account)
+            .map(|token_ids| {
+                token_ids
+                    .iter()
+                    .filter(|id| self.get().is_approved(account, **id))
+                    .copied()
+                    .collect()
+            })
+            .unwrap_or_default();
+        self.tokens_page(token_ids, offset, limit)
+    }
+
+    /// Returns the page of sorted `token_ids` and their total number
+    fn tokens_page(&self, token_ids: Vec<TokenId>, offset: u32, limit: u32) -> (Vec<Token>, u32) {
+        let tokens = token_ids
+            .iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .map(|id| self.token(*id))
+            .collect();
+        (tokens, token_ids.len() as u32)
+    }
+
+    /// Returns `limit` records of the token history starting from `offset`
+    /// (the oldest record first)
+    /// and the total number of records
+    fn history(&self, token_id: TokenId, offset: u32, limit: u32) -> (Vec<TransferRecord>, u32) {
+        let Some(records) = self.get().history.get(&token_id) else {
+            return (Vec::new(), 0);
+        };
+        let page = records
+            .iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .copied()
+            .collect();
+        (page, records.len() as u32)
+    }
+
+    /// Returns the IDs of the locked tokens of `owner` in ascending order
+    fn locked_tokens(&self, owner: &ActorId) -> Vec<TokenId> {
+        self.get()
+            .tokens_for_owner
+            .get(owner)
+            .map(|token_ids| {
+                token_ids
+                    .iter()
+                    .filter(|id| self.get().is_locked(**id))
+                    .copied()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn is_approved_for_all(&self, owner: &ActorId, operator: &ActorId) -> bool {
+        self.get()
+            .operator_approvals
+            .get(owner)
+            .map(|operators| operators.contains(operator))
+            .unwrap_or(false)
+    }
+
+    fn token_tree(&self, token_id: TokenId) -> TokenTree {
+        TokenTree {
+            token_id,
+            children: self
+                .get()
+                .children_of
+                .get(&token_id)
+                .map(|children| children.iter().map(|id| self.token_tree(*id)).collect())
+                .unwrap_or_default(),
+            balances: self
+                .get()
+                .child_balances
+                .get(&token_id)
+                .cloned()
+                .unwrap_or_default(),
+        }
     }
 
     fn proc_state(&self, query: NFTQuery) -> Option<Vec<u8>> {
@@ -145,6 +611,75 @@ pub trait NFTMetaState: NFTStateKeeper {
             NFTQuery::ApprovedTokens { account } => NFTQueryReply::ApprovedTokens {
                 tokens: self.approved_tokens(&account),
             },
+            NFTQuery::IsApprovedForAll { owner, operator } => NFTQueryReply::IsApprovedForAll {
+                approved: self.is_approved_for_all(&owner, &operator),
+            },
+            NFTQuery::UserOf { token_id } => NFTQueryReply::UserOf {
+                user: self.get().user_of(token_id),
+            },
+            NFTQuery::UserExpires { token_id } => NFTQueryReply::UserExpires {
+                expires: self.get().user_expires(token_id),
+            },
+            NFTQuery::Locked { token_id } => NFTQueryReply::Locked {
+                locked: self.get().is_soulbound(token_id) || self.get().is_locked(token_id),
+            },
+            NFTQuery::TokenTree { token_id } => NFTQueryReply::TokenTree {
+                tree: self.token_tree(token_id),
+            },
+            NFTQuery::TokenURI { token_id } => NFTQueryReply::TokenURI {
+                uri: self.token_uri(token_id),
+            },
+            NFTQuery::MaxSupply => NFTQueryReply::MaxSupply {
+                max_supply: self.get().max_supply,
+            },
+            NFTQuery::NextTokenId => NFTQueryReply::NextTokenId {
+                token_id: self.get().next_token_id(),
+            },
+            NFTQuery::AllTokensPage { offset, limit } => {
+                let (tokens, total) = self.all_tokens_page(offset, limit);
+                NFTQueryReply::AllTokensPage { tokens, total }
+            }
+            NFTQuery::TokensForOwnerPage {
+                owner,
+                offset,
+                limit,
+            } => {
+                let (tokens, total) = self.tokens_for_owner_page(&owner, offset, limit);
+                NFTQueryReply::TokensForOwnerPage { tokens, total }
+            }
+            NFTQuery::ApprovedTokensPage {
+                account,
+                offset,
+                limit,
+            } => {
+                let (tokens, total) = self.approved_tokens_page(&account, offset, limit);
+                NFTQueryReply::ApprovedTokensPage { tokens, total }
+            }
+            NFTQuery::History {
+                token_id,
+                offset,
+                limit,
+            } => {
+                let (records, total) = self.history(token_id, offset, limit);
+                NFTQueryReply::History { records, total }
+            }
+            NFTQuery::LockedTokens { owner } => NFTQueryReply::LockedTokens {
+                tokens: self.locked_tokens(&owner),
+            },
+            NFTQuery::RoyaltyInfo {
+                token_id,
+                sale_price,
+            } => {
+                let royalties = self.get().royalties_of(token_id);
+                NFTQueryReply::RoyaltyInfo {
+                    royalty_amount: royalties
+                        .map(|royalties| royalties.royalty_amount(sale_price))
+                        .unwrap_or(0),
+                    receivers: royalties
+                        .map(|royalties| royalties.royalty_payouts(sale_price))
+                        .unwrap_or_default(),
+                }
+            }
         }
         .encode();
         Some(encoded)
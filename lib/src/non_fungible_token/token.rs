@@ -11,6 +11,7 @@ pub struct Token {
     pub description: String,
     pub media: String,
     pub reference: String,
+    pub attributes: Vec<TokenAttribute>,
     pub approved_account_ids: BTreeSet<ActorId>,
 }
 
@@ -24,4 +25,38 @@ pub struct TokenMetadata {
     pub media: String,
     // URL to an off-chain JSON file with more info.
     pub reference: String,
+    // on-chain traits, ex. "Strength: 10"
+    pub attributes: Vec<TokenAttribute>,
+}
+
+#[derive(Debug, Encode, Decode, PartialEq, Eq, PartialOrd, Ord, Clone, TypeInfo, Hash)]
+pub struct TokenAttribute {
+    // ex. "Strength"
+    pub trait_type: String,
+    pub value: AttributeValue,
+    // how the value should be displayed, ex. "boost_number" or "date"
+    pub display_type: Option<String>,
+}
+
+#[derive(Debug, Encode, Decode, PartialEq, Eq, PartialOrd, Ord, Clone, TypeInfo, Hash)]
+pub enum AttributeValue {
+    String(String),
+    Number(i128),
+    Bool(bool),
+}
+
+/// A record of the provenance history of a token
+#[derive(
+    Default, Debug, Encode, Decode, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, TypeInfo, Hash,
+)]
+pub struct TransferRecord {
+    /// The zero address for mints.
+    pub from: ActorId,
+    /// The zero address for burns.
+    pub to: ActorId,
+    pub block: u32,
+    /// Block timestamp (in milliseconds).
+    pub timestamp: u64,
+    /// The sale price if the token was sold.
+    pub price: Option<u128>,
 }
@@ -0,0 +1,45 @@
+use crate::non_fungible_token::token::*;
+use gstd::{exec, msg, prelude::*, ActorId};
+
+/// Permission to mint the token on its first purchase, signed off-chain by the creator
+#[derive(Default, Debug, Encode, Decode, PartialEq, Eq, Clone, TypeInfo)]
+pub struct MintVoucher {
+    pub token_id: TokenId,
+    pub metadata: Option<TokenMetadata>,
+    /// The minimum value that must be attached to the redemption message.
+    pub min_price: u128,
+    /// The only account allowed to redeem the voucher, anyone if `None`.
+    pub recipient: Option<ActorId>,
+    /// Timestamp (in milliseconds) after which the voucher can't be redeemed.
+    pub expiry: u64,
+    pub program_id: ActorId,
+    /// The minter who signed the voucher and receives the payment.
+    pub creator: ActorId,
+}
+
+impl MintVoucher {
+    pub(crate) fn validate(&self, signature: &[u8]) {
+        if exec::program_id() != self.program_id {
+            panic!("You have tried to redeem the voucher in wrong program")
+        }
+
+        if let Some(recipient) = self.recipient {
+            if msg::source() != recipient {
+                panic!("The voucher is issued to another recipient")
+            }
+        }
+
+        if exec::block_timestamp() >= self.expiry {
+            panic!("Mint voucher has expired")
+        }
+
+        if msg::value() < self.min_price {
+            panic!("Attached value is less than the voucher price")
+        }
+
+        let creator: [u8; 32] = self.creator.into();
+        if sr25519::verify(signature, self.encode(), creator).is_err() {
+            panic!("Failed sign verification");
+        }
+    }
+}